@@ -36,13 +36,11 @@ fn new_rect_image(width: u32, height: u32, color: Rgba<u8>) -> RgbaImage {
   img
 }
 
-pub fn draw_samples(path: &AsRef<Path>, prefix: &str, samples: &[RgbaImage]) {
-  fs::create_dir_all(&path).expect(format!("Failed to create dir {:?}", &path.as_ref()).as_ref());
+pub fn draw_samples(path: &dyn AsRef<Path>, prefix: &str, samples: &[RgbaImage]) {
+  fs::create_dir_all(path).unwrap_or_else(|_| panic!("Failed to create dir {:?}", &path.as_ref()));
 
-  let mut offset = 0;
-  for s in samples {
+  for (offset, s) in samples.iter().enumerate() {
     let name = format!("{}{}.png", prefix, offset);
     s.save(Path::new(&path.as_ref()).join(name)).unwrap();
-    offset += 1;
   }
 }
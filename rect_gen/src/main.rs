@@ -10,7 +10,7 @@ mod demo;
 use demo::*;
 use docopt::Docopt;
 
-const USAGE: &'static str = "
+const USAGE: &str = "
 Usage:  rectgen [options]
 
 Options:
@@ -25,7 +25,6 @@ struct Args {
   flag_count: usize,
   flag_out: String,
   flag_prefix: String,
-  flag_help: bool,
 }
 
 fn main() {
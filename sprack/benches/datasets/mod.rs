@@ -0,0 +1,69 @@
+// shared by all bench targets, each of them uses only a part
+#![allow(dead_code)]
+
+use sprack::Dimension;
+
+// xorshift keeps the datasets identical between runs without pulling in `rand`
+pub struct Generator(u32);
+
+impl Generator {
+  pub fn new(seed: u32) -> Generator { Generator(seed) }
+
+  pub fn range(&mut self, min: u32, max: u32) -> u32 {
+    self.0 ^= self.0 << 13;
+    self.0 ^= self.0 >> 17;
+    self.0 ^= self.0 << 5;
+    min + self.0 % (max - min + 1)
+  }
+
+  pub fn pick(&mut self, values: &[u32]) -> u32 {
+    values[self.range(0, values.len() as u32 - 1) as usize]
+  }
+}
+
+pub struct Dataset {
+  pub name: &'static str,
+  pub bin_size: Dimension,
+  pub rectangles: Vec<Dimension>,
+}
+
+pub fn random_dimensions(count: usize, min: u32, max: u32) -> Vec<Dimension> {
+  let mut gen = Generator::new(0x9E37_79B9);
+  (0..count).map(|_| Dimension::new(gen.range(min, max), gen.range(min, max))).collect()
+}
+
+// square icons of a few standard sizes
+pub fn small_icons() -> Dataset {
+  let mut gen = Generator::new(1);
+  let rectangles = (0..300).map(|_| { let side = gen.pick(&[16, 24, 32]); Dimension::new(side, side) }).collect();
+  Dataset { name: "small_icons", bin_size: Dimension::new(512, 512), rectangles }
+}
+
+// wide buttons, big panels and icons mixed together
+pub fn mixed_ui() -> Dataset {
+  let mut gen = Generator::new(2);
+  let rectangles = (0..200).map(|_| match gen.range(0, 9) {
+    0..=3 => Dimension::new(gen.range(64, 256), gen.range(24, 48)),
+    4 => Dimension::new(gen.range(128, 384), gen.range(128, 384)),
+    _ => { let side = gen.range(16, 32); Dimension::new(side, side) }
+  }).collect();
+  Dataset { name: "mixed_ui", bin_size: Dimension::new(1024, 1024), rectangles }
+}
+
+// font glyphs - similar heights, widths vary a lot
+pub fn glyph_set() -> Dataset {
+  let mut gen = Generator::new(3);
+  let rectangles = (0..500).map(|_| Dimension::new(gen.range(4, 24), gen.range(20, 32))).collect();
+  Dataset { name: "glyph_set", bin_size: Dimension::new(512, 512), rectangles }
+}
+
+// few big lightmap charts
+pub fn large_lightmaps() -> Dataset {
+  let mut gen = Generator::new(4);
+  let rectangles = (0..40).map(|_| Dimension::new(gen.range(32, 512), gen.range(32, 512))).collect();
+  Dataset { name: "large_lightmaps", bin_size: Dimension::new(2048, 2048), rectangles }
+}
+
+pub fn all() -> Vec<Dataset> {
+  vec![small_icons(), mixed_ui(), glyph_set(), large_lightmaps()]
+}
@@ -0,0 +1,43 @@
+#[macro_use]
+extern crate criterion;
+extern crate sprack;
+
+mod datasets;
+
+use criterion::{Criterion, BenchmarkId};
+use sprack::*;
+
+fn bin_insert(c: &mut Criterion) {
+  let mut group = c.benchmark_group("bin_insert");
+  group.sample_size(10);
+  for &count in &[1_000, 10_000, 50_000] {
+    let dims = datasets::random_dimensions(count, 1, 16);
+    group.bench_with_input(BenchmarkId::from_parameter(count), &dims, |b, dims| b.iter(|| {
+      let mut bin = Bin::new(&Dimension::new(4096, 4096));
+      for (id, dim) in dims.iter().enumerate() {
+        bin.insert(dim, id as u32, true);
+      }
+      bin
+    }));
+  }
+  group.finish();
+}
+
+fn pack_large(c: &mut Criterion) {
+  let mut group = c.benchmark_group("pack");
+  group.sample_size(10);
+  let options = PackOptions {
+    bin_size: Dimension::new(2048, 2048),
+    flipping: true,
+    sort_heuristics: vec![heuristic_by_name("area").unwrap()],
+    ..Default::default()
+  };
+  for &count in &[1_000, 10_000, 50_000] {
+    let dims = datasets::random_dimensions(count, 1, 32);
+    group.bench_with_input(BenchmarkId::from_parameter(count), &dims, |b, dims| b.iter(|| pack(dims, &options)));
+  }
+  group.finish();
+}
+
+criterion_group!(benches, bin_insert, pack_large);
+criterion_main!(benches);
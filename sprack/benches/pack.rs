@@ -0,0 +1,73 @@
+#[macro_use]
+extern crate criterion;
+extern crate sprack;
+
+mod datasets;
+
+use criterion::{Criterion, BenchmarkId};
+use sprack::*;
+use std::cmp::max;
+use std::time::Duration;
+
+// compaction steps and growth strategy
+const GROWTH_MODES: [(u8, GrowthStrategy); 7] = [
+  (0, GrowthStrategy::Uniform),
+  (4, GrowthStrategy::Uniform),
+  (16, GrowthStrategy::Uniform),
+  (16, GrowthStrategy::ShorterSide),
+  (16, GrowthStrategy::Alternate),
+  (4, GrowthStrategy::Double),
+  (16, GrowthStrategy::HeightOnly),
+];
+
+// share of the (trimmed) atlas area covered by sprites
+fn efficiency(result: &PackResult) -> f64 {
+  let used: u64 = result.bins.iter().flat_map(|b| b.placements.iter()).map(|p| p.rect.size.area()).sum();
+  let total: u64 = result.bins.iter()
+    .map(|b| b.placements.iter().fold(Dimension::new(1, 1), |acc, p| Dimension::new(max(acc.w, p.rect.r()), max(acc.h, p.rect.b()))))
+    .map(|size| size.area())
+    .sum();
+  used as f64 / total as f64
+}
+
+fn options(dataset: &datasets::Dataset, heuristic: &str, (atlas_compact_steps, growth): (u8, GrowthStrategy)) -> PackOptions {
+  PackOptions {
+    bin_size: dataset.bin_size,
+    atlas_compact_steps,
+    growth,
+    flipping: true,
+    sort_heuristics: vec![heuristic_by_name(heuristic).unwrap()],
+    ..Default::default()
+  }
+}
+
+fn pack_datasets(c: &mut Criterion) {
+  let heuristics = PackOptions::default().sort_heuristics;
+
+  println!("{:<16} {:<22} {:>12} {:>5} {:>5} {:>10}", "dataset", "heuristic", "growth", "steps", "bins", "efficiency");
+  for dataset in datasets::all() {
+    for h in &heuristics {
+      for &mode in &GROWTH_MODES {
+        let result = &pack(&dataset.rectangles, &options(&dataset, h.name(), mode)).unwrap()[0];
+        println!("{:<16} {:<22} {:>12} {:>5} {:>5} {:>9.1}%", dataset.name, h.name(), mode.1.name(), mode.0, result.bins.len(), efficiency(result) * 100.0);
+      }
+    }
+  }
+
+  for dataset in datasets::all() {
+    let mut group = c.benchmark_group(format!("pack/{}", dataset.name));
+    group.sample_size(10).warm_up_time(Duration::from_millis(500)).measurement_time(Duration::from_secs(1));
+    for h in &heuristics {
+      for &mode in &GROWTH_MODES {
+        let options = options(&dataset, h.name(), mode);
+        group.bench_with_input(BenchmarkId::new(h.name(), format!("{}/steps={}", mode.1.name(), mode.0)), &dataset.rectangles, |b, rectangles| {
+          b.iter(|| pack(rectangles, &options))
+        });
+      }
+    }
+    group.finish();
+  }
+}
+
+criterion_group!(benches, pack_datasets);
+criterion_main!(benches);
@@ -0,0 +1,44 @@
+#![no_main]
+#[macro_use]
+extern crate libfuzzer_sys;
+#[macro_use]
+extern crate arbitrary;
+extern crate sprack;
+
+use sprack::*;
+
+#[derive(Arbitrary, Debug)]
+struct Input {
+  bin_size: (u32, u32),
+  rectangles: Vec<(u32, u32)>,
+  flipping: bool,
+}
+
+fuzz_target!(|input: Input| {
+  let size = Dimension::new(input.bin_size.0, input.bin_size.1);
+  let mut bin = Bin::new(&size);
+  let mut inserted = Vec::new();
+
+  for &(w, h) in input.rectangles.iter().take(512) {
+    let rect = Dimension::new(w, h);
+    let fits = bin.can_fit(&rect, input.flipping);
+    let id = inserted.len() as u32;
+    assert_eq!(fits, bin.insert(&rect, id, input.flipping), "can_fit disagrees with insert for {:?}", rect);
+    if fits { inserted.push(rect); }
+  }
+
+  // free space must stay inside the bin and clear of placed rectangles
+  for free in bin.free_rects() {
+    assert!(free.checked_r().is_some_and(|r| r <= size.w) && free.checked_b().is_some_and(|b| b <= size.h));
+    for p in &bin.placements {
+      let apart = free.r() <= p.rect.l() || p.rect.r() <= free.l() || free.b() <= p.rect.t() || p.rect.b() <= free.t();
+      assert!(apart, "free {:?} overlaps {:?}", free, p);
+    }
+  }
+
+  let options = PackOptions { bin_size: size, flipping: input.flipping, ..Default::default() };
+  let result = PackResult { bins: vec![bin], heuristics: heuristic_by_name("area").unwrap() };
+  if let Err(e) = validate(&result, &inserted, &options) {
+    panic!("invalid layout {:?} for {:?}", e, result.bins[0]);
+  }
+});
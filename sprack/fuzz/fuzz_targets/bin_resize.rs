@@ -0,0 +1,38 @@
+#![no_main]
+#[macro_use]
+extern crate libfuzzer_sys;
+#[macro_use]
+extern crate arbitrary;
+extern crate sprack;
+
+use sprack::*;
+
+#[derive(Arbitrary, Debug)]
+struct Input {
+  bin_size: (u32, u32),
+  rectangles: Vec<(u32, u32)>,
+  new_size: (u32, u32),
+  flipping: bool,
+}
+
+fuzz_target!(|input: Input| {
+  let mut bin = Bin::new(&Dimension::new(input.bin_size.0, input.bin_size.1));
+  let mut inserted = Vec::new();
+  for &(w, h) in input.rectangles.iter().take(512) {
+    let rect = Dimension::new(w, h);
+    if bin.insert(&rect, inserted.len() as u32, input.flipping) { inserted.push(rect); }
+  }
+
+  let before = bin.clone();
+  if !bin.resize(Dimension::new(input.new_size.0, input.new_size.1), input.flipping) {
+    // a failed resize must leave the bin untouched
+    assert_eq!(bin, before);
+    return;
+  }
+
+  let options = PackOptions { bin_size: bin.size, flipping: input.flipping, ..Default::default() };
+  let result = PackResult { bins: vec![bin], heuristics: heuristic_by_name("area").unwrap() };
+  if let Err(e) = validate(&result, &inserted, &options) {
+    panic!("invalid layout {:?} for {:?}", e, result.bins[0]);
+  }
+});
@@ -0,0 +1,47 @@
+#![no_main]
+#[macro_use]
+extern crate libfuzzer_sys;
+#[macro_use]
+extern crate arbitrary;
+extern crate sprack;
+
+use sprack::*;
+
+#[derive(Arbitrary, Debug)]
+struct Input {
+  rectangles: Vec<(u32, u32)>,
+  bin_size: (u32, u32),
+  atlas_compact_steps: u8,
+  growth: u8,
+  mode: (u8, u8),
+  flipping: bool,
+  heuristic: u8,
+}
+
+fuzz_target!(|input: Input| {
+  let rectangles = input.rectangles.iter().take(256).map(|&(w, h)| Dimension::new(w, h)).collect::<Vec<_>>();
+  let heuristics = PackOptions::default().sort_heuristics;
+  let heuristic = heuristics[input.heuristic as usize % heuristics.len()].clone();
+  let options = PackOptions {
+    bin_size: Dimension::new(input.bin_size.0, input.bin_size.1),
+    atlas_compact_steps: input.atlas_compact_steps,
+    growth: GrowthStrategy::ALL[input.growth as usize % GrowthStrategy::ALL.len()],
+    mode: match input.mode.0 % 3 {
+      0 => PackMode::Atlas,
+      1 => PackMode::Strip,
+      _ => PackMode::TextureArray { max_layers: u32::from(input.mode.1) },
+    },
+    flipping: input.flipping,
+    sort_heuristics: vec![heuristic],
+    ..Default::default()
+  };
+
+  // rejected inputs (zero sizes, pieces bigger than bin) are fine, panics and broken layouts are not
+  if let Ok(results) = pack(&rectangles, &options) {
+    for result in &results {
+      if let Err(e) = validate(result, &rectangles, &options) {
+        panic!("invalid layout {:?} for {:?}", e, result);
+      }
+    }
+  }
+});
@@ -0,0 +1,39 @@
+#![no_main]
+#[macro_use]
+extern crate libfuzzer_sys;
+#[macro_use]
+extern crate arbitrary;
+extern crate sprack;
+
+use sprack::*;
+
+#[derive(Arbitrary, Debug)]
+struct Input {
+  // sizes are kept small, every pixel of a mask gets looked at
+  sprites: Vec<(u8, u8, u64)>,
+  bin_size: (u16, u16),
+  cell: u8,
+  flipping: bool,
+  strip: bool,
+}
+
+fuzz_target!(|input: Input| {
+  let masks = input.sprites.iter().take(64)
+    .map(|&(w, h, bits)| Mask::from_fn(&Dimension::new(u32::from(w), u32::from(h)), u32::from(input.cell), |x, y| bits >> ((x + y * 7) % 64) & 1 == 1))
+    .collect::<Vec<_>>();
+  let options = PackOptions {
+    bin_size: Dimension::new(u32::from(input.bin_size.0), u32::from(input.bin_size.1)),
+    flipping: input.flipping,
+    mode: if input.strip { PackMode::Strip } else { PackMode::Atlas },
+    sort_heuristics: vec![heuristic_by_name("area").unwrap()],
+    ..Default::default()
+  };
+
+  if let Ok(results) = pack_masked(&masks, &options) {
+    for result in &results {
+      if let Err(e) = validate_masked(result, &masks, &options) {
+        panic!("invalid layout {:?} for {:?}", e, result);
+      }
+    }
+  }
+});
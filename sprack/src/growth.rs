@@ -0,0 +1,98 @@
+use structs::{Dimension, Coord};
+
+use core::cmp::{min, max};
+
+// how a compacted bin grows towards `PackOptions::bin_size` when a piece does not fit
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum GrowthStrategy {
+  // width and height together by a fixed increment
+  #[default]
+  Uniform,
+  // the shorter side only, both when they are equal
+  ShorterSide,
+  // width and height in turns
+  Alternate,
+  // both sides doubled, sizes stay powers of two if bin size is one
+  Double,
+  // bin keeps its full width and grows downwards, like a fixed-width strip
+  HeightOnly,
+  // bin keeps its full height and grows to the right
+  WidthOnly,
+}
+
+impl GrowthStrategy {
+  pub const ALL: [GrowthStrategy; 6] = [
+    GrowthStrategy::Uniform,
+    GrowthStrategy::ShorterSide,
+    GrowthStrategy::Alternate,
+    GrowthStrategy::Double,
+    GrowthStrategy::HeightOnly,
+    GrowthStrategy::WidthOnly,
+  ];
+
+  pub fn name(&self) -> &'static str {
+    match *self {
+      GrowthStrategy::Uniform => "uniform",
+      GrowthStrategy::ShorterSide => "shorter_side",
+      GrowthStrategy::Alternate => "alternate",
+      GrowthStrategy::Double => "double",
+      GrowthStrategy::HeightOnly => "height_only",
+      GrowthStrategy::WidthOnly => "width_only",
+    }
+  }
+
+  pub fn from_name(name: &str) -> Option<GrowthStrategy> {
+    GrowthStrategy::ALL.iter().find(|s| s.name() == name).cloned()
+  }
+
+  // size a new bin starts with, `steps` is `PackOptions::atlas_compact_steps`
+  pub fn initial_size<C: Coord>(&self, bin_size: &Dimension<C>, steps: u8) -> Dimension<C> {
+    let div_side = |val: C| { max(C::ONE, val / C::from_u16(u16::from(steps) + 1)) };
+    match *self {
+      GrowthStrategy::Double => {
+        let halve_side = |val: C| { max(C::ONE, val.checked_shr(u32::from(steps)).unwrap_or(C::ZERO)) };
+        dim(halve_side(bin_size.w), halve_side(bin_size.h))
+      }
+      GrowthStrategy::HeightOnly => dim(bin_size.w, div_side(bin_size.h)),
+      GrowthStrategy::WidthOnly => dim(div_side(bin_size.w), bin_size.h),
+      _ => dim(div_side(bin_size.w), div_side(bin_size.h)),
+    }
+  }
+
+  // next size to try after `current`, `None` once `bin_size` is reached. The sequence depends
+  // only on the sizes, so a failed `Bin::resize` is simply skipped and the bin moves on to the next size
+  pub fn next_size<C: Coord>(&self, current: &Dimension<C>, bin_size: &Dimension<C>, steps: u8) -> Option<Dimension<C>> {
+    let (w, h) = (current.w, current.h);
+    let (can_grow_w, can_grow_h) = (w < bin_size.w, h < bin_size.h);
+    if !can_grow_w && !can_grow_h { return None; }
+
+    let size_inc = |val: C| { max(C::ONE, val / C::from_u16(u16::from(steps) + 1)) };
+    let grow_w = || min(w.saturating_add(size_inc(bin_size.w)), bin_size.w);
+    let grow_h = || min(h.saturating_add(size_inc(bin_size.h)), bin_size.h);
+
+    let next = match *self {
+      GrowthStrategy::Uniform => dim(grow_w(), grow_h()),
+      GrowthStrategy::ShorterSide => {
+        let w_first = can_grow_w && (w <= h || !can_grow_h);
+        let h_first = can_grow_h && (h <= w || !can_grow_w);
+        dim(if w_first { grow_w() } else { w }, if h_first { grow_h() } else { h })
+      }
+      GrowthStrategy::Alternate => {
+        // the side which is relatively further from its maximum goes first, width on ties
+        let w_behind = w.widen() * bin_size.h.widen() <= h.widen() * bin_size.w.widen();
+        if can_grow_w && (w_behind || !can_grow_h) { dim(grow_w(), h) } else { dim(w, grow_h()) }
+      }
+      GrowthStrategy::Double => {
+        let two = C::from_u16(2);
+        dim(min(w.saturating_mul(two), bin_size.w), min(h.saturating_mul(two), bin_size.h))
+      }
+      GrowthStrategy::HeightOnly => if can_grow_h { dim(w, grow_h()) } else { return None; },
+      GrowthStrategy::WidthOnly => if can_grow_w { dim(grow_w(), h) } else { return None; },
+    };
+    Some(next)
+  }
+}
+
+fn dim<C>(w: C, h: C) -> Dimension<C> { Dimension { w, h } }
@@ -1,17 +1,25 @@
-use super::{Dimension, PackInput};
-
-use std::fmt::{Debug, Result, Formatter};
-use std::cmp::{Ordering, PartialOrd, max};
+use super::{Dimension, PackInput, Coord};
+
+use core::fmt::{Debug, Result, Formatter};
+use core::cmp::{Ordering, PartialOrd, max};
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+
+pub fn all<C: Coord>() -> Vec<Arc<dyn SortHeuristic<C>>> {
+  vec![
+    Arc::new(AreaSort),
+    Arc::new(PerimeterSort),
+    Arc::new(SideSort),
+    Arc::new(WidthSort),
+    Arc::new(HeightSort),
+    Arc::new(SquarenessByAreaSort),
+    Arc::new(SquarenessByPerimeterSort),
+  ]
+}
 
-pub const ALL: [&(SortHeuristic + Sync); 7] = [
-  &AreaSort,
-  &PerimeterSort,
-  &SideSort,
-  &WidthSort,
-  &HeightSort,
-  &SquarenessByAreaSort,
-  &SquarenessByPerimeterSort,
-];
+pub fn heuristic_by_name<C: Coord>(name: &str) -> Option<Arc<dyn SortHeuristic<C>>> {
+  all().into_iter().find(|h| h.name() == name)
+}
 
 pub struct AreaSort;
 
@@ -27,61 +35,61 @@ pub struct SquarenessByAreaSort;
 
 pub struct SquarenessByPerimeterSort;
 
-pub trait SortHeuristic: Sync {
+pub trait SortHeuristic<C = u32>: Send + Sync {
   fn name(&self) -> &'static str;
-  fn cmp(&self, l: &PackInput, r: &PackInput) -> Ordering;
+  fn cmp(&self, l: &PackInput<C>, r: &PackInput<C>) -> Ordering;
 }
 
-impl SortHeuristic for AreaSort {
+impl<C: Coord> SortHeuristic<C> for AreaSort {
   fn name(&self) -> &'static str { "area" }
-  fn cmp(&self, l: &PackInput, r: &PackInput) -> Ordering { cmp_by_key(l, r, |d| d.w * d.h) }
+  fn cmp(&self, l: &PackInput<C>, r: &PackInput<C>) -> Ordering { cmp_by_key(l, r, Dimension::area) }
 }
 
-impl SortHeuristic for PerimeterSort {
+impl<C: Coord> SortHeuristic<C> for PerimeterSort {
   fn name(&self) -> &'static str { "perimeter" }
-  fn cmp(&self, l: &PackInput, r: &PackInput) -> Ordering { cmp_by_key(l, r, |d| d.w + d.h) }
+  fn cmp(&self, l: &PackInput<C>, r: &PackInput<C>) -> Ordering { cmp_by_key(l, r, Dimension::perimeter) }
 }
 
-impl SortHeuristic for SideSort {
+impl<C: Coord> SortHeuristic<C> for SideSort {
   fn name(&self) -> &'static str { "side" }
-  fn cmp(&self, l: &PackInput, r: &PackInput) -> Ordering { cmp_by_key(l, r, |d| max(d.w, d.h)) }
+  fn cmp(&self, l: &PackInput<C>, r: &PackInput<C>) -> Ordering { cmp_by_key(l, r, |d| max(d.w, d.h)) }
 }
 
-impl SortHeuristic for WidthSort {
+impl<C: Coord> SortHeuristic<C> for WidthSort {
   fn name(&self) -> &'static str { "width" }
-  fn cmp(&self, l: &PackInput, r: &PackInput) -> Ordering {
+  fn cmp(&self, l: &PackInput<C>, r: &PackInput<C>) -> Ordering {
     cmp_by_key(l, r, |d| d.w)
   }
 }
 
-impl SortHeuristic for HeightSort {
+impl<C: Coord> SortHeuristic<C> for HeightSort {
   fn name(&self) -> &'static str { "height" }
-  fn cmp(&self, l: &PackInput, r: &PackInput) -> Ordering {
+  fn cmp(&self, l: &PackInput<C>, r: &PackInput<C>) -> Ordering {
     cmp_by_key(l, r, |d| d.h)
   }
 }
 
-impl SortHeuristic for SquarenessByAreaSort {
+impl<C: Coord> SortHeuristic<C> for SquarenessByAreaSort {
   fn name(&self) -> &'static str { "squareness_area" }
-  fn cmp(&self, l: &PackInput, r: &PackInput) -> Ordering { cmp_by_key(l, r, |d| sqa(d)) }
+  fn cmp(&self, l: &PackInput<C>, r: &PackInput<C>) -> Ordering { cmp_by_key(l, r, sqa) }
 }
 
-impl SortHeuristic for SquarenessByPerimeterSort {
+impl<C: Coord> SortHeuristic<C> for SquarenessByPerimeterSort {
   fn name(&self) -> &'static str { "squareness_perimeter" }
-  fn cmp(&self, l: &PackInput, r: &PackInput) -> Ordering { cmp_by_key(l, r, |d| sqp(d)) }
+  fn cmp(&self, l: &PackInput<C>, r: &PackInput<C>) -> Ordering { cmp_by_key(l, r, sqp) }
 }
 
-fn squareness(d: &Dimension) -> f32 {
-  if d.w < d.h { d.w as f32 / d.h as f32 } else { d.h as f32 / d.w as f32 }
+fn squareness<C: Coord>(d: &Dimension<C>) -> f32 {
+  if d.w < d.h { d.w.to_f32() / d.h.to_f32() } else { d.h.to_f32() / d.w.to_f32() }
 }
 
-fn sqa(d: &Dimension) -> f32 { squareness(d) * (d.w * d.h) as f32 }
+fn sqa<C: Coord>(d: &Dimension<C>) -> f32 { squareness(d) * C::wide_to_f32(d.area()) }
 
-fn sqp(d: &Dimension) -> f32 { squareness(d) * (d.w + d.h) as f32 }
+fn sqp<C: Coord>(d: &Dimension<C>) -> f32 { squareness(d) * C::wide_to_f32(d.perimeter()) }
 
-fn cmp_by_key<F, T: PartialOrd>(l: &PackInput, r: &PackInput, key: F) -> Ordering
-  where F: Fn(&Dimension) -> T { key(&r.dim).partial_cmp(&key(&l.dim)).unwrap_or(Ordering::Equal) }
+fn cmp_by_key<C, F, T: PartialOrd>(l: &PackInput<C>, r: &PackInput<C>, key: F) -> Ordering
+  where F: Fn(&Dimension<C>) -> T { key(&r.dim).partial_cmp(&key(&l.dim)).unwrap_or(Ordering::Equal) }
 
-impl Debug for SortHeuristic {
+impl<C> Debug for dyn SortHeuristic<C> {
   fn fmt(&self, f: &mut Formatter) -> Result { write!(f, "{}", self.name()) }
 }
@@ -1,22 +1,62 @@
+// only `alloc` is needed, threads come with the optional `rayon` feature
+#![no_std]
+
+#[macro_use]
+extern crate alloc;
+#[cfg(feature = "rayon")]
 extern crate rayon;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(feature = "serde")]
+#[macro_use]
+extern crate serde_derive;
 
 mod structs;
 mod heuristics;
-
-pub use structs::{Dimension, PackInput, PackResult, PackErr, PackOptions, Bin};
-pub use heuristics::{SortHeuristic, AreaSort, PerimeterSort, SideSort, WidthSort, HeightSort, SquarenessByAreaSort, SquarenessByPerimeterSort};
+mod growth;
+mod validation;
+mod masked;
+mod progress;
+#[cfg(feature = "serde")]
+mod serialization;
+
+pub use structs::{Coord, Dimension, Rectangle, Placement, PackInput, PackResult, PackErr, PackOptions, PackMode, Bin};
+pub use structs::Mask;
+pub use masked::pack_masked;
+pub use validation::{validate, validate_masked, LayoutErr};
+pub use growth::GrowthStrategy;
+pub use progress::{Progress, ProgressHook, CancelToken};
+pub use heuristics::{SortHeuristic, heuristic_by_name, AreaSort, PerimeterSort, SideSort, WidthSort, HeightSort, SquarenessByAreaSort, SquarenessByPerimeterSort};
 
 use self::structs::*;
 use self::heuristics::*;
+use self::progress::*;
 
-use std::cmp::{min, max};
+use core::cmp::max;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+#[cfg(feature = "rayon")]
 use rayon::prelude::*;
 
 
-pub fn pack<'a>(rectangles: &[Dimension], options: &'a PackOptions) -> Result<Vec<PackResult<'a>>, PackErr> {
+pub fn pack<C: Coord>(rectangles: &[Dimension<C>], options: &PackOptions<C>) -> Result<Vec<PackResult<C>>, PackErr> {
+  check_input(rectangles, options)?;
+
+  let results = pack_each_heuristic(rectangles, options, |sorted, tracker| match options.mode {
+    PackMode::Atlas | PackMode::TextureArray { .. } => pack_sorted(sorted, options, tracker),
+    PackMode::Strip => pack_strip(sorted, options, tracker),
+  })?;
+  select_layers(results, options)
+}
+
+fn check_input<C: Coord>(rectangles: &[Dimension<C>], options: &PackOptions<C>) -> Result<(), PackErr> {
+  let bin_size = options.max_bin_size();
   if options.sort_heuristics.is_empty() { return Err(PackErr("No heuristics supplied")); };
+  if options.mode == (PackMode::TextureArray { max_layers: 0 }) { return Err(PackErr("Texture array needs at least one layer")); };
+  if bin_size.is_empty() { return Err(PackErr("Bin size must not be zero")); };
+  if rectangles.iter().any(Dimension::is_empty) { return Err(PackErr("Some pieces have zero size")); };
 
-  let dimension_bigger_than_bin = |r: &Dimension| match options.bin_size.fits(r) {
+  let dimension_bigger_than_bin = |r: &Dimension<C>| match bin_size.fits(r) {
     Fit::No => { true }
     Fit::Yes(flip) | Fit::Exact(flip) => flip && !options.flipping
   };
@@ -24,60 +64,109 @@ pub fn pack<'a>(rectangles: &[Dimension], options: &'a PackOptions) -> Result<Ve
   if rectangles.iter().any(dimension_bigger_than_bin) {
     return Err(PackErr("Some pieces do not fit bin size"));
   }
+  Ok(())
+}
 
+// sorts inputs with every heuristic and packs them with `pack_fn`, in parallel if possible.
+// `pack_fn` may stop early once the tracker is cancelled, its bins are thrown away then
+fn pack_each_heuristic<C, F>(rectangles: &[Dimension<C>], options: &PackOptions<C>, pack_fn: F) -> Result<Vec<PackResult<C>>, PackErr>
+  where C: Coord, F: Fn(&[PackInput<C>], &Tracker<C>) -> Vec<Bin<C>> + Sync {
+  let tracker = Tracker::new(options, rectangles.len());
   let inputs = rectangles.iter().enumerate()
     .map(|(idx, dim)| { PackInput { id: idx as u32, dim: *dim } }).collect::<Vec<_>>();
 
-  let results: Vec<PackResult> = options.sort_heuristics.par_iter()
-    .map(|&h| {
-      let mut cloned = inputs.to_owned();
-      cloned.sort_unstable_by(|l, r| h.cmp(l, r));
+  let pack_with = |h: &Arc<dyn SortHeuristic<C>>| {
+    let mut cloned = inputs.to_vec();
+    // ties are broken by id, otherwise their order would depend on the size of the coordinate type
+    cloned.sort_unstable_by(|l, r| h.cmp(l, r).then(l.id.cmp(&r.id)));
+    let bins = pack_fn(&cloned, &tracker);
+    tracker.heuristic_done();
+    PackResult { heuristics: Arc::clone(h), bins }
+  };
+
+  #[cfg(feature = "rayon")]
+  let results = options.sort_heuristics.par_iter().map(pack_with).collect::<Vec<_>>();
+  #[cfg(not(feature = "rayon"))]
+  let results = options.sort_heuristics.iter().map(pack_with).collect::<Vec<_>>();
 
-      let bins = pack_sorted(&cloned, options);
-      PackResult { heuristics: h, bins }
-    })
-    .collect::<Vec<_>>();
+  if tracker.cancelled() { return Err(PackErr("Cancelled")); }
+  Ok(results)
+}
 
+fn select_layers<C: Coord>(mut results: Vec<PackResult<C>>, options: &PackOptions<C>) -> Result<Vec<PackResult<C>>, PackErr> {
+  if let PackMode::TextureArray { max_layers } = options.mode {
+    results.retain(|r| r.layer_count() <= max_layers as usize);
+    if results.is_empty() { return Err(PackErr("Pieces do not fit into allowed number of layers")); }
+    results.sort_by_key(PackResult::layer_count);
+  }
   Ok(results)
 }
 
-fn pack_sorted(rectangles: &[PackInput], options: &PackOptions) -> Vec<Bin> {
-  let mut bins: Vec<Bin> = vec![new_bin(options)];
-  let insert_fn: &'static Fn(&mut Bin, &Dimension, u32, &PackOptions) -> bool =
-    if options.atlas_compact_steps == 0 { &try_insert } else { &try_insert_with_growth };
+type InsertFn<C> = fn(&mut Bin<C>, &Dimension<C>, u32, &PackOptions<C>) -> bool;
+
+fn pack_sorted<C: Coord>(rectangles: &[PackInput<C>], options: &PackOptions<C>, tracker: &Tracker<C>) -> Vec<Bin<C>> {
+  let mut bins: Vec<Bin<C>> = vec![new_bin(options)];
+  let insert_fn: InsertFn<C> = if compacts(options) { try_insert_with_growth } else { try_insert };
 
   for &input in rectangles {
+    if tracker.cancelled() { break; }
     let packed = bins.iter_mut().any(|bin| insert_fn(bin, &input.dim, input.id, options));
     if !packed {
+      // a compacted bin may start smaller than the piece, so it has to grow like the others
       let mut new_bin = new_bin(options);
+      insert_fn(&mut new_bin, &input.dim, input.id, options);
+      bins.push(new_bin);
+    }
+    tracker.piece_placed();
+  }
+  bins
+}
+
+fn pack_strip<C: Coord>(rectangles: &[PackInput<C>], options: &PackOptions<C>, tracker: &Tracker<C>) -> Vec<Bin<C>> {
+  let bin_size = options.max_bin_size();
+  let mut bins = vec![Bin::new(&bin_size)];
+  for &input in rectangles {
+    if tracker.cancelled() { break; }
+    // another bin is only needed once the strip runs out of coordinate range
+    if !bins.iter_mut().any(|bin| bin.insert(&input.dim, input.id, options.flipping)) {
+      let mut new_bin = Bin::new(&bin_size);
       new_bin.insert(&input.dim, input.id, options.flipping);
       bins.push(new_bin);
     }
+    tracker.piece_placed();
+  }
+
+  for bin in &mut bins {
+    let height = bin.placements.iter().map(|p| p.rect.b()).max().unwrap_or(C::ZERO);
+    bin.crop(Dimension { w: bin_size.w, h: max(C::ONE, height) });
   }
   bins
 }
 
-fn new_bin(options: &PackOptions) -> Bin {
-  if options.atlas_compact_steps == 0 { return Bin::new(&options.bin_size); }
-  let size_divisor = u32::from(options.atlas_compact_steps) + 1;
-  let div_side = |val: u32| { max(1, val / size_divisor) };
-  Bin::new(&Dimension::new(div_side(options.bin_size.w), div_side(options.bin_size.h)))
+// texture array layers must all have the same size
+fn compacts<C: Coord>(options: &PackOptions<C>) -> bool {
+  options.atlas_compact_steps > 0 && options.mode == PackMode::Atlas
 }
 
-fn try_insert(bin: &mut Bin, rect: &Dimension, id: u32, options: &PackOptions) -> bool {
+fn new_bin<C: Coord>(options: &PackOptions<C>) -> Bin<C> {
+  if !compacts(options) { return Bin::new(&options.bin_size); }
+  Bin::new(&options.growth.initial_size(&options.bin_size, options.atlas_compact_steps))
+}
+
+fn try_insert<C: Coord>(bin: &mut Bin<C>, rect: &Dimension<C>, id: u32, options: &PackOptions<C>) -> bool {
   bin.insert(rect, id, options.flipping)
 }
 
-fn try_insert_with_growth(bin: &mut Bin, rect: &Dimension, id: u32, options: &PackOptions) -> bool {
-  let size_inc = |val: u32| { max(1, val / u32::from(options.atlas_compact_steps) + 1) };
-  let mut current_size = bin.size;
-  while !bin.insert(rect, id, options.flipping) {
-    if current_size.w >= options.bin_size.w && current_size.h >= options.bin_size.h { return false; }
-    current_size = Dimension::new(
-      min(current_size.w + size_inc(options.bin_size.w), options.bin_size.w),
-      min(current_size.h + size_inc(options.bin_size.h), options.bin_size.h),
-    );
-    if !bin.resize(current_size, options.flipping) { continue }
+fn try_insert_with_growth<C: Coord>(bin: &mut Bin<C>, rect: &Dimension<C>, id: u32, options: &PackOptions<C>) -> bool {
+  if bin.insert(rect, id, options.flipping) { return true; }
+
+  let mut size = bin.size;
+  while let Some(next) = options.growth.next_size(&size, &options.bin_size, options.atlas_compact_steps) {
+    // growing is the slow part, caller notices the cancellation right after
+    if is_cancelled(options) { return false; }
+    size = next;
+    // bin is left as is when resize fails, so there is no point to retry the insert
+    if bin.resize(size, options.flipping) && bin.insert(rect, id, options.flipping) { return true; }
   }
-  true
+  false
 }
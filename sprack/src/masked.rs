@@ -0,0 +1,128 @@
+use structs::*;
+use super::{check_input, pack_each_heuristic, select_layers};
+
+use core::cmp::max;
+use alloc::vec::Vec;
+
+const MAX_COLUMNS: u32 = 1 << 16;
+
+// pieces are placed on a grid of mask cells, so their bounding boxes may overlap as long as opaque
+// cells do not. Bins are never compacted, strips grow by whole cells
+pub fn pack_masked(masks: &[Mask], options: &PackOptions) -> Result<Vec<PackResult>, PackErr> {
+  let rectangles = masks.iter().map(Mask::size).collect::<Vec<_>>();
+  check_input(&rectangles, options)?;
+
+  let cell = masks.first().map_or(options.max_bin_size().w, Mask::cell);
+  if masks.iter().any(|m| m.cell() != cell) { return Err(PackErr("Masks have different cell sizes")); }
+  // a row of cells is allocated for the whole bin width
+  if options.max_bin_size().w / cell > MAX_COLUMNS { return Err(PackErr("Mask cells are too small for bin width")); }
+
+  let rotated = if options.flipping { masks.iter().map(Mask::rotated).collect() } else { Vec::new() };
+  let shapes = masks.iter().enumerate()
+    .map(|(i, m)| (Shape::new(m), rotated.get(i).map(Shape::new)))
+    .collect::<Vec<_>>();
+
+  let results = pack_each_heuristic(&rectangles, options, |sorted, tracker| {
+    let mut sheets: Vec<Sheet> = Vec::new();
+    for input in sorted {
+      if tracker.cancelled() { break; }
+      let shape = &shapes[input.id as usize];
+      if !sheets.iter_mut().any(|sheet| sheet.insert(shape, input.id)) {
+        let mut sheet = Sheet::new(options, cell);
+        sheet.insert(shape, input.id);
+        sheets.push(sheet);
+      }
+      tracker.piece_placed();
+    }
+    if sheets.is_empty() { sheets.push(Sheet::new(options, cell)); }
+    sheets.into_iter().map(|sheet| sheet.into_bin(options)).collect()
+  })?;
+  select_layers(results, options)
+}
+
+// piece size with offsets of its opaque cells
+struct Shape {
+  size: Dimension,
+  rows: u32,
+  opaque: Vec<(u32, u32)>,
+}
+
+impl Shape {
+  fn new(mask: &Mask) -> Shape {
+    let opaque = (0..mask.rows()).flat_map(|row| (0..mask.cols()).map(move |col| (col, row)))
+      .filter(|&(col, row)| mask.is_opaque(col, row))
+      .collect();
+    Shape { size: mask.size(), rows: mask.rows(), opaque }
+  }
+}
+
+// occupied cells of a bin, rows are added as pieces need them
+struct Sheet {
+  size: Dimension,
+  cell: u32,
+  cols: u32,
+  rows: u32,
+  occupied: Vec<bool>,
+  placements: Vec<Placement>,
+}
+
+impl Sheet {
+  fn new(options: &PackOptions, cell: u32) -> Sheet {
+    let size = options.max_bin_size();
+    Sheet { size, cell, cols: size.w.div_ceil(cell), rows: 0, occupied: Vec::new(), placements: Vec::new() }
+  }
+
+  fn insert(&mut self, (shape, rotated): &(Shape, Option<Shape>), id: u32) -> bool {
+    let found = self.find_position(shape).map(|pos| (pos, shape, false))
+      .or_else(|| rotated.as_ref().and_then(|r| self.find_position(r).map(|pos| (pos, r, true))));
+    let ((col, row), shape, flipped) = match found {
+      Some(found) => found,
+      None => return false,
+    };
+
+    let rows = row + shape.rows;
+    if rows > self.rows {
+      self.occupied.resize(rows as usize * self.cols as usize, false);
+      self.rows = rows;
+    }
+    for &(c, r) in &shape.opaque {
+      let index = self.index(col + c, row + r);
+      self.occupied[index] = true;
+    }
+    let rect = Rectangle { x: col * self.cell, y: row * self.cell, size: shape.size, flipped };
+    self.placements.push(Placement { index: id, rect });
+    true
+  }
+
+  // first free position row by row, rows below the used ones are always free
+  fn find_position(&self, shape: &Shape) -> Option<(u32, u32)> {
+    let fits_in = |pos: u32, side: u32, limit: u32| pos.checked_mul(self.cell)
+      .and_then(|p| p.checked_add(side))
+      .is_some_and(|end| end <= limit);
+
+    for row in 0..=self.rows {
+      if !fits_in(row, shape.size.h, self.size.h) { return None; }
+      for col in (0..self.cols).take_while(|&col| fits_in(col, shape.size.w, self.size.w)) {
+        let collides = shape.opaque.iter()
+          .any(|&(c, r)| row + r < self.rows && self.occupied[self.index(col + c, row + r)]);
+        if !collides { return Some((col, row)); }
+      }
+    }
+    None
+  }
+
+  fn index(&self, col: u32, row: u32) -> usize {
+    row as usize * self.cols as usize + col as usize
+  }
+
+  fn into_bin(self, options: &PackOptions) -> Bin {
+    let size = match options.mode {
+      PackMode::Strip => {
+        let height = self.placements.iter().map(|p| p.rect.b()).max().unwrap_or(0);
+        Dimension::new(self.size.w, max(1, height))
+      }
+      _ => self.size,
+    };
+    Bin::from_placements(&size, self.placements)
+  }
+}
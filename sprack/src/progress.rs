@@ -0,0 +1,80 @@
+use structs::{PackOptions, Coord};
+
+use core::fmt::{Debug, Result, Formatter};
+use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use alloc::sync::Arc;
+
+// snapshot passed to `PackOptions::progress`, heuristics run in parallel so every one of them
+// has to place all rectangles
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Progress {
+  pub heuristics_done: usize,
+  pub heuristics: usize,
+  pub placed: usize,
+  pub rectangles: usize,
+}
+
+// called from the packing threads, possibly concurrently and not strictly in order
+#[derive(Clone)]
+pub struct ProgressHook(Arc<dyn Fn(Progress) + Send + Sync>);
+
+impl ProgressHook {
+  pub fn new<F: Fn(Progress) + Send + Sync + 'static>(hook: F) -> ProgressHook {
+    ProgressHook(Arc::new(hook))
+  }
+}
+
+impl Debug for ProgressHook {
+  fn fmt(&self, f: &mut Formatter) -> Result {
+    write!(f, "ProgressHook")
+  }
+}
+
+// clones share the flag, cancelled `pack` returns `PackErr("Cancelled")`
+#[derive(Clone, Debug, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+  pub fn new() -> CancelToken { CancelToken::default() }
+
+  pub fn cancel(&self) { self.0.store(true, Ordering::Relaxed); }
+
+  pub fn is_cancelled(&self) -> bool { self.0.load(Ordering::Relaxed) }
+}
+
+pub fn is_cancelled<C: Coord>(options: &PackOptions<C>) -> bool {
+  options.cancel.as_ref().is_some_and(CancelToken::is_cancelled)
+}
+
+// progress of a single `pack` call shared by all heuristics
+pub struct Tracker<'a, C: Coord + 'a> {
+  options: &'a PackOptions<C>,
+  rectangles: usize,
+  placed: AtomicUsize,
+  heuristics_done: AtomicUsize,
+}
+
+impl<'a, C: Coord> Tracker<'a, C> {
+  pub fn new(options: &'a PackOptions<C>, rectangles: usize) -> Tracker<'a, C> {
+    let rectangles = rectangles * options.sort_heuristics.len();
+    Tracker { options, rectangles, placed: AtomicUsize::new(0), heuristics_done: AtomicUsize::new(0) }
+  }
+
+  pub fn cancelled(&self) -> bool { is_cancelled(self.options) }
+
+  pub fn piece_placed(&self) {
+    let placed = self.placed.fetch_add(1, Ordering::Relaxed) + 1;
+    self.report(placed, self.heuristics_done.load(Ordering::Relaxed));
+  }
+
+  pub fn heuristic_done(&self) {
+    let done = self.heuristics_done.fetch_add(1, Ordering::Relaxed) + 1;
+    self.report(self.placed.load(Ordering::Relaxed), done);
+  }
+
+  fn report(&self, placed: usize, heuristics_done: usize) {
+    if let Some(ProgressHook(ref hook)) = self.options.progress {
+      hook(Progress { heuristics_done, heuristics: self.options.sort_heuristics.len(), placed, rectangles: self.rectangles });
+    }
+  }
+}
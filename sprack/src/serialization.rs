@@ -0,0 +1,38 @@
+// heuristics are trait objects, so they travel by name and are resolved back to the built-in ones
+pub mod heuristic {
+  use {Coord, SortHeuristic, heuristic_by_name};
+
+  use alloc::string::String;
+  use alloc::sync::Arc;
+  use serde::{Serializer, Deserializer, Deserialize};
+  use serde::de::Error;
+
+  pub fn serialize<C, S: Serializer>(h: &Arc<dyn SortHeuristic<C>>, s: S) -> Result<S::Ok, S::Error> {
+    s.serialize_str(h.name())
+  }
+
+  pub fn deserialize<'de, C: Coord, D: Deserializer<'de>>(d: D) -> Result<Arc<dyn SortHeuristic<C>>, D::Error> {
+    let name = String::deserialize(d)?;
+    heuristic_by_name(&name).ok_or_else(|| D::Error::custom(format!("unknown heuristic `{}`", name)))
+  }
+}
+
+pub mod heuristic_list {
+  use {Coord, SortHeuristic, heuristic_by_name};
+
+  use alloc::string::String;
+  use alloc::sync::Arc;
+  use alloc::vec::Vec;
+  use serde::{Serializer, Deserializer, Deserialize};
+  use serde::de::Error;
+
+  pub fn serialize<C, S: Serializer>(hs: &[Arc<dyn SortHeuristic<C>>], s: S) -> Result<S::Ok, S::Error> {
+    s.collect_seq(hs.iter().map(|h| h.name()))
+  }
+
+  pub fn deserialize<'de, C: Coord, D: Deserializer<'de>>(d: D) -> Result<Vec<Arc<dyn SortHeuristic<C>>>, D::Error> {
+    Vec::<String>::deserialize(d)?.iter()
+      .map(|name| heuristic_by_name(name).ok_or_else(|| D::Error::custom(format!("unknown heuristic `{}`", name))))
+      .collect()
+  }
+}
@@ -1,31 +1,65 @@
 use super::*;
+use validation::find_overlap;
 
+#[cfg(feature = "serde")]
+use core::convert::TryFrom;
+
+// only size and placements are serialized, the tree is carved again around placements on load
 #[derive(Clone, PartialEq, Eq, Debug)]
-pub struct Bin {
-  pub size: Dimension,
-  pub placements: Vec<Placement>,
-  node: Box<Node>,
-  last_rejected_size: Dimension,
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(into = "BinData<C>", try_from = "BinData<C>"))]
+#[cfg_attr(feature = "serde", serde(bound(serialize = "C: Coord + ::serde::Serialize", deserialize = "C: Coord + ::serde::Deserialize<'de>")))]
+pub struct Bin<C = u32> {
+  pub size: Dimension<C>,
+  pub placements: Vec<Placement<C>>,
+  tree: NodeTree<C>,
+  last_rejected_size: Dimension<C>,
+  // packed by masks, bounding boxes of placements may overlap
+  masked: bool,
+}
+
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+struct BinData<C> {
+  size: Dimension<C>,
+  placements: Vec<Placement<C>>,
+  #[serde(default, skip_serializing_if = "::core::ops::Not::not")]
+  masked: bool,
 }
 
-impl Bin {
-  pub fn new(size: &Dimension) -> Bin {
+impl<C: Coord> Bin<C> {
+  pub fn new(size: &Dimension<C>) -> Bin<C> {
     Bin {
       size: *size,
       placements: Vec::new(),
-      node: Box::new(Node::new(size)),
+      tree: NodeTree::new(size),
       last_rejected_size: *size,
+      masked: false,
     }
   }
 
-  pub fn insert(&mut self, rect: &Dimension, id: u32, flipping_allowed: bool) -> bool {
-    // short-circuit if rect is bigger than last rejected one
-    match self.last_rejected_size.fits(rect) {
-      Fit::No => { return false; }
-      Fit::Yes(flip) | Fit::Exact(flip) => if flip && !flipping_allowed { return false; }
-    }
+  // bin of masked packing without any free space, placements are not checked
+  pub(crate) fn from_placements(size: &Dimension<C>, placements: Vec<Placement<C>>) -> Bin<C> {
+    let empty = Dimension { w: C::ZERO, h: C::ZERO };
+    let mut tree = NodeTree::new(size);
+    tree.crop(&empty);
+    Bin { size: *size, placements, tree, last_rejected_size: empty, masked: true }
+  }
+
+  // bin around already made placements, free space between them stays usable.
+  // Fails if some placement is empty, does not lie inside the bin or overlaps another one
+  pub fn restore(size: &Dimension<C>, placements: Vec<Placement<C>>) -> core::result::Result<Bin<C>, PackErr> {
+    check_inside(size, &placements)?;
+    if find_overlap(&placements, &|_, _| true).is_some() { return Err(PackErr("Placements overlap")); }
+
+    let tree = NodeTree::carve(size, &placements);
+    Ok(Bin { size: *size, placements, tree, last_rejected_size: *size, masked: false })
+  }
+
+  pub fn insert(&mut self, rect: &Dimension<C>, id: u32, flipping_allowed: bool) -> bool {
+    if self.rejects(rect, flipping_allowed) { return false; }
 
-    if let Some(rect) = self.node.insert(rect, id, flipping_allowed) {
+    if let Some(rect) = self.tree.insert(rect, id, flipping_allowed) {
       self.placements.push(Placement { rect, index: id });
       true
     } else {
@@ -34,24 +68,82 @@ impl Bin {
     }
   }
 
-  pub fn resize(&mut self, new_size: Dimension, flipping_allowed: bool) -> bool {
-    let new_size = Dimension { w: max(1, new_size.w), h: max(1, new_size.h) };
+  // answers whether `insert` would succeed, without modifying the bin
+  pub fn can_fit(&self, rect: &Dimension<C>, flipping_allowed: bool) -> bool {
+    !self.rejects(rect, flipping_allowed) && self.tree.can_insert(rect, flipping_allowed)
+  }
+
+  // unoccupied areas of the bin, they do not overlap each other
+  pub fn free_rects<'a>(&'a self) -> impl Iterator<Item = Rectangle<C>> + 'a {
+    self.tree.free_leaves().map(|node| node.bounds)
+  }
+
+  pub fn resize(&mut self, new_size: Dimension<C>, flipping_allowed: bool) -> bool {
+    let new_size = Dimension { w: max(C::ONE, new_size.w), h: max(C::ONE, new_size.h) };
 
     // reinsert all rectangles into bigger node
-    let mut new_node = Node::new(&new_size);
+    let mut new_tree = NodeTree::with_capacity(&new_size, self.tree.len());
     let mut placements = Vec::with_capacity(self.placements.len());
     for placement in &self.placements {
-      if let Some(rect) = new_node.insert(&placement.rect.non_flipped_size(), placement.index, flipping_allowed) {
+      if let Some(rect) = new_tree.insert(&placement.rect.non_flipped_size(), placement.index, flipping_allowed) {
         placements.push(Placement { rect, index: placement.index });
       } else {
         // due to heuristics it sometimes happen that bigger node fails to fit rectangles the smaller one was able to
         return false;
       }
     }
-    self.node = Box::new(new_node);
+    self.tree = new_tree;
     self.placements = placements;
     self.size = new_size;
     self.last_rejected_size = new_size;
     true
   }
+
+  // drops free space outside of `new_size`, fails without changes if some placement is not inside
+  pub fn crop(&mut self, new_size: Dimension<C>) -> bool {
+    let outside = |p: &Placement<C>| p.rect.r() > new_size.w || p.rect.b() > new_size.h;
+    if self.placements.iter().any(outside) { return false; }
+
+    self.tree.crop(&new_size);
+    self.size = new_size;
+    self.last_rejected_size = new_size;
+    true
+  }
+
+  fn rejects(&self, rect: &Dimension<C>, flipping_allowed: bool) -> bool {
+    if rect.is_empty() { return true; }
+
+    // short-circuit if rect is bigger than last rejected one
+    match self.last_rejected_size.fits(rect) {
+      Fit::No => true,
+      Fit::Yes(flip) | Fit::Exact(flip) => flip && !flipping_allowed,
+    }
+  }
+}
+
+fn check_inside<C: Coord>(size: &Dimension<C>, placements: &[Placement<C>]) -> core::result::Result<(), PackErr> {
+  if size.is_empty() { return Err(PackErr("Bin size must not be zero")); }
+  let inside = |p: &Placement<C>| !p.rect.size.is_empty()
+    && p.rect.checked_r().is_some_and(|r| r <= size.w)
+    && p.rect.checked_b().is_some_and(|b| b <= size.h);
+  if placements.iter().all(inside) { Ok(()) } else { Err(PackErr("Placement lies outside of the bin")) }
+}
+
+#[cfg(feature = "serde")]
+impl<C: Coord> From<Bin<C>> for BinData<C> {
+  fn from(bin: Bin<C>) -> BinData<C> {
+    BinData { size: bin.size, placements: bin.placements, masked: bin.masked }
+  }
+}
+
+#[cfg(feature = "serde")]
+impl<C: Coord> TryFrom<BinData<C>> for Bin<C> {
+  type Error = &'static str;
+
+  fn try_from(data: BinData<C>) -> core::result::Result<Bin<C>, &'static str> {
+    if !data.masked { return Bin::restore(&data.size, data.placements).map_err(|err| err.0); }
+    // only opaque cells of masked placements are apart, so they are not checked for overlaps
+    check_inside(&data.size, &data.placements).map_err(|err| err.0)?;
+    Ok(Bin::from_placements(&data.size, data.placements))
+  }
 }
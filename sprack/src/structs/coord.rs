@@ -0,0 +1,49 @@
+use core::fmt::Debug;
+use core::hash::Hash;
+use core::ops::{Add, Mul, Sub, Div};
+
+// unsigned integer used for positions and sizes, `u32` unless asked otherwise
+pub trait Coord: Copy + Ord + Default + Debug + Hash + Send + Sync + 'static + Sub<Output = Self> + Div<Output = Self> {
+  // wide enough for areas and perimeters
+  type Wide: Copy + Ord + Debug + Add<Output = Self::Wide> + Mul<Output = Self::Wide>;
+
+  const ZERO: Self;
+  const ONE: Self;
+  const MAX: Self;
+
+  fn from_u16(val: u16) -> Self;
+  fn checked_add(self, rhs: Self) -> Option<Self>;
+  fn checked_sub(self, rhs: Self) -> Option<Self>;
+  fn saturating_add(self, rhs: Self) -> Self;
+  fn saturating_mul(self, rhs: Self) -> Self;
+  fn checked_shr(self, rhs: u32) -> Option<Self>;
+  fn widen(self) -> Self::Wide;
+  fn to_f32(self) -> f32;
+  fn wide_to_f32(wide: Self::Wide) -> f32;
+}
+
+macro_rules! impl_coord {
+  ($t:ty, $wide:ty) => {
+    impl Coord for $t {
+      type Wide = $wide;
+
+      const ZERO: $t = 0;
+      const ONE: $t = 1;
+      const MAX: $t = <$t>::MAX;
+
+      fn from_u16(val: u16) -> $t { <$t>::from(val) }
+      fn checked_add(self, rhs: $t) -> Option<$t> { <$t>::checked_add(self, rhs) }
+      fn checked_sub(self, rhs: $t) -> Option<$t> { <$t>::checked_sub(self, rhs) }
+      fn saturating_add(self, rhs: $t) -> $t { <$t>::saturating_add(self, rhs) }
+      fn saturating_mul(self, rhs: $t) -> $t { <$t>::saturating_mul(self, rhs) }
+      fn checked_shr(self, rhs: u32) -> Option<$t> { <$t>::checked_shr(self, rhs) }
+      fn widen(self) -> $wide { <$wide>::from(self) }
+      fn to_f32(self) -> f32 { self as f32 }
+      fn wide_to_f32(wide: $wide) -> f32 { wide as f32 }
+    }
+  }
+}
+
+impl_coord!(u16, u32);
+impl_coord!(u32, u64);
+impl_coord!(u64, u128);
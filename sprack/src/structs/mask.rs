@@ -0,0 +1,110 @@
+use super::*;
+
+#[cfg(feature = "serde")]
+use core::convert::TryFrom;
+
+// coarse occupancy of a sprite, split into `cell` x `cell` squares starting at the top left corner.
+// Cells at the right and bottom edges may be cut by the sprite size
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "MaskData"))]
+pub struct Mask {
+  size: Dimension,
+  cell: u32,
+  cols: u32,
+  rows: u32,
+  // row by row
+  cells: Vec<bool>,
+}
+
+// the same fields, checked to agree with each other on load
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct MaskData {
+  size: Dimension,
+  cell: u32,
+  cols: u32,
+  rows: u32,
+  cells: Vec<bool>,
+}
+
+impl Mask {
+  // fully opaque mask, the same as packing by bounding box
+  pub fn new(size: &Dimension, cell: u32) -> Mask {
+    Mask::from_fn(size, cell, |_, _| true)
+  }
+
+  // `opaque` is asked about pixels until one is found per cell
+  pub fn from_fn<F: Fn(u32, u32) -> bool>(size: &Dimension, cell: u32, opaque: F) -> Mask {
+    let cell = max(1, cell);
+    let (cols, rows) = (size.w.div_ceil(cell), size.h.div_ceil(cell));
+    let mut cells = Vec::with_capacity(cols as usize * rows as usize);
+    for row in 0..rows {
+      for col in 0..cols {
+        let (l, t) = (col * cell, row * cell);
+        let (r, b) = (min(l.saturating_add(cell), size.w), min(t.saturating_add(cell), size.h));
+        cells.push((t..b).any(|y| (l..r).any(|x| opaque(x, y))));
+      }
+    }
+    Mask { size: *size, cell, cols, rows, cells }
+  }
+
+  pub fn size(&self) -> Dimension { self.size }
+  pub fn cell(&self) -> u32 { self.cell }
+  pub fn cols(&self) -> u32 { self.cols }
+  pub fn rows(&self) -> u32 { self.rows }
+
+  pub fn is_opaque(&self, col: u32, row: u32) -> bool {
+    col < self.cols && row < self.rows && self.cells[(row * self.cols + col) as usize]
+  }
+
+  pub fn set_opaque(&mut self, col: u32, row: u32, opaque: bool) {
+    assert!(col < self.cols && row < self.rows, "cell ({}, {}) is outside of mask", col, row);
+    self.cells[(row * self.cols + col) as usize] = opaque;
+  }
+
+  // pixel areas of opaque cells relative to the sprite
+  pub fn opaque_rects<'a>(&'a self) -> impl Iterator<Item = Rectangle> + 'a {
+    let cell = self.cell;
+    (0..self.rows).flat_map(move |row| (0..self.cols).map(move |col| (col, row)))
+      .filter(move |&(col, row)| self.is_opaque(col, row))
+      .map(move |(col, row)| {
+        let (x, y) = (col * cell, row * cell);
+        let size = Dimension::new(min(cell, self.size.w - x), min(cell, self.size.h - y));
+        Rectangle { x, y, size, flipped: false }
+      })
+  }
+
+  // mask of the sprite rotated by 90deg counter-clockwise, which is how flipped placements are drawn.
+  // Rotated cells are not aligned with the original ones when width is not a multiple of cell size,
+  // each of them is opaque if any original cell it touches is
+  pub fn rotated(&self) -> Mask {
+    let (w, cell) = (self.size.w, self.cell);
+    let size = Dimension::new(self.size.h, w);
+    let (cols, rows) = (size.w.div_ceil(cell), size.h.div_ceil(cell));
+    let mut cells = Vec::with_capacity(cols as usize * rows as usize);
+    for row in 0..rows {
+      // rotated pixel (x, y) comes from (w - 1 - y, x)
+      let first_col = (w - min((row + 1).saturating_mul(cell), w)) / cell;
+      let last_col = (w - 1 - row * cell) / cell;
+      for col in 0..cols {
+        cells.push((first_col..=last_col).any(|c| self.is_opaque(c, col)));
+      }
+    }
+    Mask { size, cell, cols, rows, cells }
+  }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<MaskData> for Mask {
+  type Error = &'static str;
+
+  fn try_from(data: MaskData) -> core::result::Result<Mask, &'static str> {
+    if data.cell == 0 { return Err("Mask cell size must not be zero"); }
+    if (data.cols, data.rows) != (data.size.w.div_ceil(data.cell), data.size.h.div_ceil(data.cell)) {
+      return Err("Mask columns and rows do not match its size and cell");
+    }
+    if Some(data.cells.len()) != (data.cols as usize).checked_mul(data.rows as usize) { return Err("Mask cells do not match its columns and rows"); }
+    Ok(Mask { size: data.size, cell: data.cell, cols: data.cols, rows: data.rows, cells: data.cells })
+  }
+}
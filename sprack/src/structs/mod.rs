@@ -1,22 +1,46 @@
 mod bin;
 mod node;
 mod options;
+mod mask;
+mod coord;
 
 pub use self::bin::*;
 pub use self::node::*;
 pub use self::options::*;
-use super::{SortHeuristic, ALL as DEFAULT_HEURISTICS};
+pub use self::mask::*;
+pub use self::coord::*;
+use super::{SortHeuristic, GrowthStrategy, ProgressHook, CancelToken, all as default_heuristics};
 
-use std::cmp::max;
-use std::fmt::{Debug, Result, Formatter};
+use core::cmp::{min, max};
+use core::fmt::{Debug, Result, Formatter};
+use alloc::sync::Arc;
+use alloc::vec::Vec;
 
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
-pub struct PackInput { pub dim: Dimension, pub id: u32 }
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PackInput<C = u32> { pub dim: Dimension<C>, pub id: u32 }
 
-pub struct PackResult<'a> { pub bins: Vec<Bin>, pub heuristics: &'a SortHeuristic }
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound(serialize = "C: Coord + ::serde::Serialize", deserialize = "C: Coord + ::serde::Deserialize<'de>")))]
+pub struct PackResult<C: Coord = u32> {
+  pub bins: Vec<Bin<C>>,
+  #[cfg_attr(feature = "serde", serde(with = "::serialization::heuristic"))]
+  pub heuristics: Arc<dyn SortHeuristic<C>>,
+}
+
+impl<C: Coord> PackResult<C> {
+  // bins of a texture array are its layers
+  pub fn layer_count(&self) -> usize { self.bins.len() }
+
+  // all placements along with index of the bin (layer) they are in
+  pub fn layered_placements<'a>(&'a self) -> impl Iterator<Item = (usize, &'a Placement<C>)> + 'a {
+    self.bins.iter().enumerate().flat_map(|(layer, bin)| bin.placements.iter().map(move |p| (layer, p)))
+  }
+}
 
-impl<'a> Debug for PackResult<'a> {
+impl<C: Coord> Debug for PackResult<C> {
   fn fmt(&self, f: &mut Formatter) -> Result {
     write!(f, "{}[{}]", self.heuristics.name(), self.bins.len())
   }
@@ -26,20 +50,23 @@ impl<'a> Debug for PackResult<'a> {
 pub struct PackErr(pub &'static str);
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
-pub struct Dimension { pub w: u32, pub h: u32 }
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Dimension<C = u32> { pub w: C, pub h: C }
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
-pub struct Rectangle {
-  pub x: u32,
-  pub y: u32,
-  pub size: Dimension,
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Rectangle<C = u32> {
+  pub x: C,
+  pub y: C,
+  pub size: Dimension<C>,
   pub flipped: bool,
 }
 
 #[derive(Clone, PartialEq, Eq, Debug)]
-pub struct Placement {
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Placement<C = u32> {
   pub index: u32,
-  pub rect: Rectangle,
+  pub rect: Rectangle<C>,
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
@@ -47,12 +74,24 @@ pub enum Fit { No, Yes(bool), Exact(bool) } // bool is for `flipped`
 
 // ===============================================================================================
 
+// only for `u32`, so that plain literals keep working. Other coordinates use struct literals
 impl Dimension {
   pub fn new(w: u32, h: u32) -> Dimension {
     Dimension { w, h }
   }
+}
+
+impl<C: Coord> Dimension<C> {
+  pub fn is_empty(&self) -> bool { self.w == C::ZERO || self.h == C::ZERO }
+
+  pub fn area(&self) -> C::Wide { self.w.widen() * self.h.widen() }
+
+  pub fn perimeter(&self) -> C::Wide {
+    let half = self.w.widen() + self.h.widen();
+    half + half
+  }
 
-  pub fn fits(&self, inner: &Dimension) -> Fit {
+  pub fn fits(&self, inner: &Dimension<C>) -> Fit {
     if self.w == inner.w && self.h == inner.h { return Fit::Exact(false); }
     if self.h == inner.w && self.w == inner.h { return Fit::Exact(true); }
     if self.w >= inner.w && self.h >= inner.h { return Fit::Yes(false); }
@@ -61,12 +100,15 @@ impl Dimension {
   }
 }
 
-impl Rectangle {
-  pub fn t(&self) -> u32 { self.y }
-  pub fn l(&self) -> u32 { self.x }
-  pub fn b(&self) -> u32 { self.y + self.size.h }
-  pub fn r(&self) -> u32 { self.x + self.size.w }
-  pub fn non_flipped_size(&self) -> Dimension {
-    if self.flipped { Dimension::new(self.size.h, self.size.w) } else { self.size }
+impl<C: Coord> Rectangle<C> {
+  pub fn t(&self) -> C { self.y }
+  pub fn l(&self) -> C { self.x }
+  // saturate instead of overflowing, rectangles produced by the packer never reach `C::MAX` anyway
+  pub fn b(&self) -> C { self.y.saturating_add(self.size.h) }
+  pub fn r(&self) -> C { self.x.saturating_add(self.size.w) }
+  pub fn checked_b(&self) -> Option<C> { self.y.checked_add(self.size.h) }
+  pub fn checked_r(&self) -> Option<C> { self.x.checked_add(self.size.w) }
+  pub fn non_flipped_size(&self) -> Dimension<C> {
+    if self.flipped { Dimension { w: self.size.h, h: self.size.w } } else { self.size }
   }
 }
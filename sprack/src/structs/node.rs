@@ -1,81 +1,233 @@
 use super::*;
 
 
-#[derive(Clone, PartialEq, Eq, Debug)]
-pub struct Node {
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Node<C = u32> {
   pub id: Option<u32>,
-  pub bounds: Rectangle,
-  pub child1: Option<Box<Node>>,
-  pub child2: Option<Box<Node>>,
+  pub bounds: Rectangle<C>,
+  pub children: Option<(usize, usize)>,
+  pub parent: Option<usize>,
+  // largest free width and height among leaves of this subtree, not necessarily of the same leaf
+  pub free: Dimension<C>,
 }
 
-impl Node {
-  pub fn new(size: &Dimension) -> Node {
-    Node::from_rect(Rectangle { x: 0, y: 0, size: *size, flipped: false })
+// all nodes live in one vector and refer to each other by index, root is at 0
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct NodeTree<C = u32> {
+  nodes: Vec<Node<C>>,
+}
+
+// line splitting a node in two, at an absolute x or y coordinate
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Cut<C> { X(C), Y(C) }
+
+impl<C: Coord> Node<C> {
+  pub fn new(size: &Dimension<C>) -> Node<C> {
+    Node::from_rect(Rectangle { x: C::ZERO, y: C::ZERO, size: *size, flipped: false })
   }
 
-  pub fn from_bound_box(l: u32, t: u32, r: u32, b: u32) -> Node {
-    Node::from_rect(Rectangle { x: l, y: t, size: Dimension { w: r - l, h: b - t }, flipped: false })
+  pub fn from_bound_box(l: C, t: C, r: C, b: C) -> Option<Node<C>> {
+    let size = Dimension { w: r.checked_sub(l)?, h: b.checked_sub(t)? };
+    Some(Node::from_rect(Rectangle { x: l, y: t, size, flipped: false }))
+  }
+
+  pub fn from_rect(bounds: Rectangle<C>) -> Node<C> {
+    Node { id: None, bounds, children: None, parent: None, free: bounds.size }
+  }
+}
+
+impl<C: Coord> NodeTree<C> {
+  pub fn new(size: &Dimension<C>) -> NodeTree<C> {
+    NodeTree::with_capacity(size, 1)
   }
 
-  pub fn from_rect(bounds: Rectangle) -> Node {
-    Node { id: None, bounds, child1: None, child2: None }
+  pub fn with_capacity(size: &Dimension<C>, capacity: usize) -> NodeTree<C> {
+    let mut nodes = Vec::with_capacity(max(1, capacity));
+    nodes.push(Node::new(size));
+    NodeTree { nodes }
   }
 
-  pub fn insert(&mut self, rect: &Dimension, id: u32, flipping_allowed: bool) -> Option<Rectangle> {
-    // attempt insert 1st child
-    if let Some(ref mut child1) = self.child1 {
-      let rect = child1.insert(rect, id, flipping_allowed);
-      // if inserted - we're done
-      if rect.is_some() { return rect; }
+  // tree with `placements` as its occupied leaves and free leaves around them. Placements must be
+  // inside `size` and must not overlap, areas no guillotine cut can separate are left occupied
+  pub fn carve(size: &Dimension<C>, placements: &[Placement<C>]) -> NodeTree<C> {
+    let mut tree = NodeTree::with_capacity(size, 2 * placements.len() + 1);
+    let mut pending = vec![(0, placements.iter().collect::<Vec<_>>())];
+    while let Some((index, inside)) = pending.pop() {
+      let bounds = tree.nodes[index].bounds;
+      let cut = match inside.as_slice() {
+        [] => continue,
+        [p] if p.rect.x == bounds.x && p.rect.y == bounds.y && p.rect.size == bounds.size => None,
+        _ => find_cut(&bounds, &inside),
+      };
+
+      match cut.and_then(|cut| tree.split_at(index, cut).map(|child1| (cut, child1))) {
+        Some((cut, child1)) => {
+          let (first, second) = inside.into_iter().partition(|p| match cut {
+            Cut::X(x) => p.rect.r() <= x,
+            Cut::Y(y) => p.rect.b() <= y,
+          });
+          pending.push((child1, first));
+          pending.push((child1 + 1, second));
+        }
+        None => {
+          let node = &mut tree.nodes[index];
+          node.id = Some(inside[0].index);
+          node.bounds.flipped = inside[0].rect.flipped;
+        }
+      }
     }
+    tree.refresh_free();
+    tree
+  }
+
+  pub fn len(&self) -> usize { self.nodes.len() }
+
+  pub fn insert(&mut self, rect: &Dimension<C>, id: u32, flipping_allowed: bool) -> Option<Rectangle<C>> {
+    let mut current = self.find_leaf(0, rect, flipping_allowed)?;
+    loop {
+      let node = self.nodes[current];
+      match node.bounds.size.fits(rect) {
+        // the rectangle perfectly fits the node (maybe if we flip it by 90deg)
+        Fit::Exact(flip) => {
+          let bounds = Rectangle { flipped: flip, ..node.bounds };
+          let node = &mut self.nodes[current];
+          node.id = Some(id);
+          node.bounds = bounds;
+          node.free = Dimension { w: C::ZERO, h: C::ZERO };
+          self.update_free(current);
+          return Some(bounds);
+        }
 
-    // attempt insert 2nd child
-    if let Some(ref mut child2) = self.child2 {
-      return child2.insert(rect, id, flipping_allowed);
+        // the node can fit the rectangle (maybe if we flip it by 90deg) - split it and go on with the 1st child
+        Fit::Yes(flip) => {
+          let (w, h) = if flip { (rect.h, rect.w) } else { (rect.w, rect.h) };
+          let child1 = self.split(current, w, h)?;
+          current = self.find_leaf(child1, rect, flipping_allowed)?;
+        }
+
+        Fit::No => { return None; }
+      }
     }
+  }
 
-    // so it is leaf
-    // return if there's already id
-    if self.id.is_some() { return None; }
+  pub fn can_insert(&self, rect: &Dimension<C>, flipping_allowed: bool) -> bool {
+    self.find_leaf(0, rect, flipping_allowed).is_some()
+  }
 
-    let fit = self.bounds.size.fits(rect);
+  pub fn free_leaves<'a>(&'a self) -> impl Iterator<Item = &'a Node<C>> + 'a {
+    self.nodes.iter().filter(|node| node.children.is_none() && node.id.is_none() && !node.bounds.size.is_empty())
+  }
 
-    match fit {
-      // node is too small for rectangle - return nothing
-      Fit::No => { return None; }
+  // cuts all nodes to `size`, nodes lying outside of it become empty. Occupied nodes must be inside
+  pub fn crop(&mut self, size: &Dimension<C>) {
+    for node in &mut self.nodes {
+      let b = node.bounds;
+      let (l, t) = (min(b.l(), size.w), min(b.t(), size.h));
+      let (r, bottom) = (min(b.r(), size.w), min(b.b(), size.h));
+      node.bounds = Rectangle { x: l, y: t, size: Dimension { w: r - l, h: bottom - t }, ..b };
+    }
+    self.refresh_free();
+  }
 
-      // the node can fit the rectangle (maybe if we flip it by 90deg)
-      Fit::Yes(flip) => if flip && !flipping_allowed { return None; } else { self.bounds.flipped = flip; }
+  fn refresh_free(&mut self) {
+    // children are always stored after their parent, walking backwards refreshes them first
+    for index in (0..self.nodes.len()).rev() {
+      let node = self.nodes[index];
+      self.nodes[index].free = match node.children {
+        Some((child1, child2)) => {
+          let (free1, free2) = (self.nodes[child1].free, self.nodes[child2].free);
+          Dimension { w: max(free1.w, free2.w), h: max(free1.h, free2.h) }
+        }
+        None if node.id.is_none() => node.bounds.size,
+        None => Dimension { w: C::ZERO, h: C::ZERO },
+      };
+    }
+  }
 
-      // the rectangle perfectly fits the node (maybe if we flip it by 90deg)
-      Fit::Exact(flip) => {
-        if flip && !flipping_allowed { return None; }
-        self.id = Some(id);
-        self.bounds.flipped = flip;
-        return Some(self.bounds);
+  // depth-first search for the first free leaf accepting `rect`, starting at `start` and walking
+  // the tree in the same order the recursive version did: 1st child subtree, then the 2nd one
+  fn find_leaf(&self, start: usize, rect: &Dimension<C>, flipping_allowed: bool) -> Option<usize> {
+    let mut current = start;
+    loop {
+      let node = &self.nodes[current];
+      // whole subtree is skipped if none of its free leaves can take the rectangle
+      if accepts(&node.free, rect, flipping_allowed) {
+        match node.children {
+          Some((child1, _)) => { current = child1; continue; }
+          None => if node.id.is_none() { return Some(current); },
+        }
       }
-    }
 
-    let (w, h) = if self.bounds.flipped { (rect.h, rect.w) } else { (rect.w, rect.h) };
+      // go to the next sibling, climbing up while we are the 2nd child
+      loop {
+        if current == start { return None; }
+        let parent = self.nodes[current].parent?;
+        let (child1, child2) = self.nodes[parent].children?;
+        if current == child1 {
+          current = child2;
+          break;
+        }
+        current = parent;
+      }
+    }
+  }
 
-    let b = self.bounds;
+  fn split(&mut self, index: usize, w: C, h: C) -> Option<usize> {
+    let b = self.nodes[index].bounds;
     // decide to split node horizontally or vertically
-    if self.bounds.size.w - w > self.bounds.size.h - h {
+    let cut = if b.size.w.checked_sub(w)? > b.size.h.checked_sub(h)? {
       // split horizontally [|]
-      self.child1 = Some(Box::new(Node::from_bound_box(b.l(), b.t(), b.l() + w, b.b())));
-      self.child2 = Some(Box::new(Node::from_bound_box(b.l() + w, b.t(), b.r(), b.b())));
+      Cut::X(b.l().checked_add(w)?)
     } else {
       // split vertically [-]
-      self.child1 = Some(Box::new(Node::from_bound_box(b.l(), b.t(), b.r(), b.t() + h)));
-      self.child2 = Some(Box::new(Node::from_bound_box(b.l(), b.t() + h, b.r(), b.b())));
-    }
+      Cut::Y(b.t().checked_add(h)?)
+    };
+    self.split_at(index, cut)
+  }
 
-    if let Some(ref mut child1) = self.child1 {
-      child1.insert(rect, id, flipping_allowed)
-    } else {
-      println!("no child1 !?!?!?");
-      None
+  fn split_at(&mut self, index: usize, cut: Cut<C>) -> Option<usize> {
+    let b = self.nodes[index].bounds;
+    let (r, bottom) = (b.checked_r()?, b.checked_b()?);
+    let (mut child1, mut child2) = match cut {
+      Cut::X(x) => (Node::from_bound_box(b.l(), b.t(), x, bottom)?, Node::from_bound_box(x, b.t(), r, bottom)?),
+      Cut::Y(y) => (Node::from_bound_box(b.l(), b.t(), r, y)?, Node::from_bound_box(b.l(), y, r, bottom)?),
+    };
+    child1.parent = Some(index);
+    child2.parent = Some(index);
+
+    let child1_index = self.nodes.len();
+    self.nodes.push(child1);
+    self.nodes.push(child2);
+    self.nodes[index].children = Some((child1_index, child1_index + 1));
+    Some(child1_index)
+  }
+
+  // propagate free space changes upwards so that search can skip subtrees without room
+  fn update_free(&mut self, index: usize) {
+    let mut current = index;
+    while let Some(parent) = self.nodes[current].parent {
+      let (child1, child2) = self.nodes[parent].children.expect("parent node has children");
+      let (free1, free2) = (self.nodes[child1].free, self.nodes[child2].free);
+      let free = Dimension { w: max(free1.w, free2.w), h: max(free1.h, free2.h) };
+      if self.nodes[parent].free == free { break; }
+      self.nodes[parent].free = free;
+      current = parent;
     }
   }
 }
+
+fn accepts<C: Coord>(free: &Dimension<C>, rect: &Dimension<C>, flipping_allowed: bool) -> bool {
+  (rect.w <= free.w && rect.h <= free.h) || (flipping_allowed && rect.h <= free.w && rect.w <= free.h)
+}
+
+// a line through `bounds` along some placement edge that crosses none of the placements
+fn find_cut<C: Coord>(bounds: &Rectangle<C>, placements: &[&Placement<C>]) -> Option<Cut<C>> {
+  let crosses_x = |x: C| placements.iter().any(|p| p.rect.l() < x && x < p.rect.r());
+  let crosses_y = |y: C| placements.iter().any(|p| p.rect.t() < y && y < p.rect.b());
+
+  let x = placements.iter().flat_map(|p| [p.rect.l(), p.rect.r()])
+    .find(|&x| bounds.l() < x && x < bounds.r() && !crosses_x(x));
+  let y = || placements.iter().flat_map(|p| [p.rect.t(), p.rect.b()])
+    .find(|&y| bounds.t() < y && y < bounds.b() && !crosses_y(y));
+  x.map(Cut::X).or_else(|| y().map(Cut::Y))
+}
@@ -1,21 +1,69 @@
-use super::{SortHeuristic, Dimension};
+use super::{SortHeuristic, Dimension, Coord, GrowthStrategy, ProgressHook, CancelToken};
 
-pub struct PackOptions<'a> {
-  pub bin_size: Dimension,
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum PackMode {
+  // bins are at most `bin_size`
+  #[default]
+  Atlas,
+  // bins are `bin_size.w` wide and grow downwards without limit, `bin_size.h` and compaction
+  // are ignored. Bin size reports the height actually used
+  Strip,
+  // bins are layers of a texture array, all of `bin_size` with no compaction. Only results with
+  // at most `max_layers` layers are returned, those with fewer layers first
+  TextureArray { max_layers: u32 },
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default = "PackOptions::with_defaults"))]
+#[cfg_attr(feature = "serde", serde(bound(serialize = "C: Coord + ::serde::Serialize", deserialize = "C: Coord + ::serde::Deserialize<'de>")))]
+pub struct PackOptions<C: Coord = u32> {
+  pub bin_size: Dimension<C>,
   pub atlas_compact_steps: u8,
+  pub growth: GrowthStrategy,
+  pub mode: PackMode,
   pub flipping: bool,
   pub trim: bool,
-  pub sort_heuristics: &'a [&'a (SortHeuristic + Sync)],
+  #[cfg_attr(feature = "serde", serde(with = "::serialization::heuristic_list"))]
+  pub sort_heuristics: Vec<Arc<dyn SortHeuristic<C>>>,
+  #[cfg_attr(feature = "serde", serde(skip))]
+  pub progress: Option<ProgressHook>,
+  #[cfg_attr(feature = "serde", serde(skip))]
+  pub cancel: Option<CancelToken>,
 }
 
-impl<'a> Default for PackOptions<'a> {
-  fn default() -> PackOptions<'a> {
+// only for `u32` like `Dimension::new`, other coordinates start with `PackOptions::with_defaults`
+impl Default for PackOptions {
+  fn default() -> PackOptions {
+    PackOptions::with_defaults()
+  }
+}
+
+impl<C: Coord> PackOptions<C> {
+  pub fn with_defaults() -> PackOptions<C> {
     PackOptions {
-      bin_size: Dimension::new(512, 512),
+      bin_size: Dimension { w: C::from_u16(512), h: C::from_u16(512) },
       atlas_compact_steps: 0,
+      growth: GrowthStrategy::default(),
+      mode: PackMode::default(),
       flipping: false,
       trim: false,
-      sort_heuristics: &super::DEFAULT_HEURISTICS,
+      sort_heuristics: super::default_heuristics(),
+      progress: None,
+      cancel: None,
+    }
+  }
+
+  // largest bin `pack` may produce
+  pub fn max_bin_size(&self) -> Dimension<C> {
+    match self.mode {
+      PackMode::Atlas | PackMode::TextureArray { .. } => self.bin_size,
+      PackMode::Strip => Dimension { w: self.bin_size.w, h: C::MAX },
     }
   }
 }
@@ -0,0 +1,105 @@
+use structs::*;
+
+use alloc::vec::Vec;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LayoutErr {
+  // id placed more than once
+  Duplicate(u32),
+  // id never placed
+  Missing(u32),
+  // id that does not correspond to any input rectangle
+  UnknownId(u32),
+  // bin is bigger than `PackOptions::max_bin_size` allows
+  OversizedBin { bin: usize },
+  // texture array layer is not exactly `PackOptions::bin_size`
+  LayerSize { bin: usize },
+  // more bins than `PackMode::TextureArray` allows
+  TooManyLayers,
+  OutOfBounds { bin: usize, id: u32 },
+  Overlap { bin: usize, ids: (u32, u32) },
+  // placed size matches neither the input nor the input rotated by 90deg
+  SizeMismatch { bin: usize, id: u32 },
+  // rotated placement while `PackOptions::flipping` is off
+  UnexpectedFlip { bin: usize, id: u32 },
+}
+
+pub fn validate<C: Coord>(result: &PackResult<C>, rectangles: &[Dimension<C>], options: &PackOptions<C>) -> Result<(), LayoutErr> {
+  validate_with(result, rectangles, options, &|_, _| true)
+}
+
+// like `validate`, but pieces only overlap if their opaque cells do. Flipped pieces use `Mask::rotated`
+pub fn validate_masked(result: &PackResult, masks: &[Mask], options: &PackOptions) -> Result<(), LayoutErr> {
+  let rectangles = masks.iter().map(Mask::size).collect::<Vec<_>>();
+  let rotated = if options.flipping { masks.iter().map(Mask::rotated).collect() } else { Vec::new() };
+
+  let opaque_rects = |p: &Placement| {
+    let mask = if p.rect.flipped { &rotated[p.index as usize] } else { &masks[p.index as usize] };
+    mask.opaque_rects().map(|r| Rectangle { x: r.x + p.rect.x, y: r.y + p.rect.y, ..r }).collect::<Vec<_>>()
+  };
+  let collide = |a: &Placement, b: &Placement| {
+    let b_rects = opaque_rects(b);
+    opaque_rects(a).iter().any(|ra| b_rects.iter().any(|rb| intersect(ra, rb)))
+  };
+  validate_with(result, &rectangles, options, &collide)
+}
+
+// `collide` decides whether placements with intersecting bounds really overlap
+fn validate_with<C: Coord>(result: &PackResult<C>, rectangles: &[Dimension<C>], options: &PackOptions<C>, collide: &dyn Fn(&Placement<C>, &Placement<C>) -> bool) -> Result<(), LayoutErr> {
+  let mut placed = vec![false; rectangles.len()];
+  let max_bin_size = options.max_bin_size();
+  if let PackMode::TextureArray { max_layers } = options.mode {
+    if result.layer_count() > max_layers as usize { return Err(LayoutErr::TooManyLayers); }
+  }
+
+  for (bin_idx, bin) in result.bins.iter().enumerate() {
+    if bin.size.w > max_bin_size.w || bin.size.h > max_bin_size.h {
+      return Err(LayoutErr::OversizedBin { bin: bin_idx });
+    }
+    if let PackMode::TextureArray { .. } = options.mode {
+      if bin.size != options.bin_size { return Err(LayoutErr::LayerSize { bin: bin_idx }); }
+    }
+
+    for p in &bin.placements {
+      let id = p.index;
+      let input = rectangles.get(id as usize).ok_or(LayoutErr::UnknownId(id))?;
+      if placed[id as usize] { return Err(LayoutErr::Duplicate(id)); }
+      placed[id as usize] = true;
+
+      if p.rect.non_flipped_size() != *input { return Err(LayoutErr::SizeMismatch { bin: bin_idx, id }); }
+      if p.rect.flipped && !options.flipping { return Err(LayoutErr::UnexpectedFlip { bin: bin_idx, id }); }
+
+      let inside = p.rect.checked_r().is_some_and(|r| r <= bin.size.w)
+        && p.rect.checked_b().is_some_and(|b| b <= bin.size.h);
+      if !inside { return Err(LayoutErr::OutOfBounds { bin: bin_idx, id }); }
+    }
+
+    if let Some(ids) = find_overlap(&bin.placements, collide) {
+      return Err(LayoutErr::Overlap { bin: bin_idx, ids });
+    }
+  }
+
+  match placed.iter().position(|&p| !p) {
+    Some(id) => Err(LayoutErr::Missing(id as u32)),
+    None => Ok(()),
+  }
+}
+
+// sweep along x axis, only rectangles whose horizontal spans intersect are compared
+pub(crate) fn find_overlap<C: Coord>(placements: &[Placement<C>], collide: &dyn Fn(&Placement<C>, &Placement<C>) -> bool) -> Option<(u32, u32)> {
+  let mut sorted = placements.iter().collect::<Vec<_>>();
+  sorted.sort_by_key(|p| p.rect.x);
+
+  for (i, a) in sorted.iter().enumerate() {
+    for b in sorted[i + 1..].iter().take_while(|b| b.rect.l() < a.rect.r()) {
+      if b.rect.t() < a.rect.b() && a.rect.t() < b.rect.b() && collide(a, b) {
+        return Some((a.index, b.index));
+      }
+    }
+  }
+  None
+}
+
+fn intersect(a: &Rectangle, b: &Rectangle) -> bool {
+  a.l() < b.r() && b.l() < a.r() && a.t() < b.b() && b.t() < a.b()
+}
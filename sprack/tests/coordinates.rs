@@ -0,0 +1,73 @@
+extern crate proptest;
+extern crate sprack;
+
+use proptest::prelude::*;
+use sprack::*;
+
+fn convert<C: Coord, D: Coord>(dim: &Dimension<C>, f: &dyn Fn(C) -> D) -> Dimension<D> {
+  Dimension { w: f(dim.w), h: f(dim.h) }
+}
+
+// the same layout for u16, u32 and u64 as long as all values fit
+fn same_layouts<C: Coord>(a: &[PackResult<C>], b: &[PackResult], f: &dyn Fn(C) -> u32) -> bool {
+  a.len() == b.len() && a.iter().zip(b).all(|(a, b)| {
+    a.bins.len() == b.bins.len() && a.bins.iter().zip(&b.bins).all(|(a, b)| {
+      convert(&a.size, f) == b.size && a.placements.iter().zip(&b.placements).all(|(a, b)| {
+        a.index == b.index && a.rect.flipped == b.rect.flipped && f(a.rect.x) == b.rect.x && f(a.rect.y) == b.rect.y
+          && convert(&a.rect.size, f) == b.rect.size
+      })
+    })
+  })
+}
+
+proptest! {
+  #[test]
+  fn narrow_and_wide_coordinates_pack_the_same(sides in prop::collection::vec((1u16..=64, 1u16..=64), 0..48), steps in 0u8..=8, flipping in any::<bool>()) {
+    let rectangles16 = sides.iter().map(|&(w, h)| Dimension { w, h }).collect::<Vec<_>>();
+    let rectangles32 = rectangles16.iter().map(|d| convert(d, &u32::from)).collect::<Vec<_>>();
+    let rectangles64 = rectangles16.iter().map(|d| convert(d, &u64::from)).collect::<Vec<_>>();
+
+    let options32 = PackOptions { bin_size: Dimension::new(96, 80), atlas_compact_steps: steps, flipping, ..Default::default() };
+    let options16 = PackOptions { bin_size: Dimension { w: 96u16, h: 80 }, atlas_compact_steps: steps, flipping, ..PackOptions::with_defaults() };
+    let options64 = PackOptions { bin_size: Dimension { w: 96u64, h: 80 }, atlas_compact_steps: steps, flipping, ..PackOptions::with_defaults() };
+
+    let results32 = pack(&rectangles32, &options32).unwrap();
+    let results16 = pack(&rectangles16, &options16).unwrap();
+    let results64 = pack(&rectangles64, &options64).unwrap();
+    for result in &results16 {
+      prop_assert_eq!(validate(result, &rectangles16, &options16), Ok(()));
+    }
+    for result in &results64 {
+      prop_assert_eq!(validate(result, &rectangles64, &options64), Ok(()));
+    }
+    prop_assert!(same_layouts(&results16, &results32, &u32::from));
+    prop_assert!(same_layouts(&results64, &results32, &|v| v as u32));
+  }
+}
+
+#[test]
+fn wide_coordinates_go_beyond_u32() {
+  let side = u64::from(u32::MAX) * 4;
+  let rectangles = [Dimension { w: side / 2, h: side }, Dimension { w: side / 2, h: side / 3 }, Dimension { w: 7, h: 5 }];
+  let options = PackOptions { bin_size: Dimension { w: side, h: side }, flipping: true, ..PackOptions::with_defaults() };
+  for result in &pack(&rectangles, &options).unwrap() {
+    assert_eq!(validate(result, &rectangles, &options), Ok(()));
+    assert_eq!(result.bins.len(), 1);
+  }
+}
+
+#[test]
+fn narrow_coordinates_saturate() {
+  let max = u16::MAX;
+  let rectangles = [Dimension { w: max, h: 3 }, Dimension { w: 3, h: max - 3 }, Dimension { w: max - 3, h: max - 3 }];
+  for &steps in &[0, 1, 255] {
+    let options = PackOptions { bin_size: Dimension { w: max, h: max }, atlas_compact_steps: steps, ..PackOptions::with_defaults() };
+    for result in &pack(&rectangles, &options).unwrap() {
+      assert_eq!(validate(result, &rectangles, &options), Ok(()));
+    }
+  }
+
+  let options = PackOptions { bin_size: Dimension { w: 16u16, h: 0 }, mode: PackMode::Strip, ..PackOptions::with_defaults() };
+  let result = &pack(&[Dimension { w: 16, h: 40000 }, Dimension { w: 16, h: 20000 }], &options).unwrap()[0];
+  assert_eq!(result.bins.iter().map(|b| b.size.h).sum::<u16>(), 60000);
+}
@@ -0,0 +1,57 @@
+extern crate sprack;
+
+use sprack::*;
+
+fn sizes(growth: GrowthStrategy, bin_size: Dimension, steps: u8) -> Vec<Dimension> {
+  let mut sizes = vec![growth.initial_size(&bin_size, steps)];
+  while let Some(next) = growth.next_size(sizes.last().unwrap(), &bin_size, steps) {
+    sizes.push(next);
+  }
+  sizes
+}
+
+fn dims(sides: &[(u32, u32)]) -> Vec<Dimension> {
+  sides.iter().map(|&(w, h)| Dimension::new(w, h)).collect()
+}
+
+#[test]
+fn strategies_grow_towards_bin_size() {
+  let bin_sizes = [Dimension::new(1, 1), Dimension::new(7, 300), Dimension::new(512, 512), Dimension::new(u32::MAX, 3)];
+  for &growth in &GrowthStrategy::ALL {
+    for bin_size in &bin_sizes {
+      for &steps in &[1, 2, 16, 255] {
+        let sizes = sizes(growth, *bin_size, steps);
+        for pair in sizes.windows(2) {
+          assert!(pair[1].w >= pair[0].w && pair[1].h >= pair[0].h, "{:?} shrinks: {:?}", growth, pair);
+          assert!(pair[1] != pair[0], "{:?} stalls at {:?}", growth, pair[0]);
+        }
+        assert_eq!(sizes.last(), Some(bin_size), "{:?}", growth);
+      }
+    }
+  }
+}
+
+#[test]
+fn strategy_size_sequences() {
+  let bin_size = Dimension::new(8, 4);
+  assert_eq!(sizes(GrowthStrategy::Uniform, bin_size, 3), dims(&[(2, 1), (4, 2), (6, 3), (8, 4)]));
+  assert_eq!(sizes(GrowthStrategy::ShorterSide, bin_size, 3), dims(&[(2, 1), (2, 2), (4, 3), (4, 4), (6, 4), (8, 4)]));
+  assert_eq!(sizes(GrowthStrategy::Alternate, bin_size, 3), dims(&[(2, 1), (4, 1), (4, 2), (6, 2), (6, 3), (8, 3), (8, 4)]));
+  assert_eq!(sizes(GrowthStrategy::Double, bin_size, 2), dims(&[(2, 1), (4, 2), (8, 4)]));
+  assert_eq!(sizes(GrowthStrategy::HeightOnly, bin_size, 3), dims(&[(8, 1), (8, 2), (8, 3), (8, 4)]));
+  assert_eq!(sizes(GrowthStrategy::WidthOnly, bin_size, 3), dims(&[(2, 4), (4, 4), (6, 4), (8, 4)]));
+}
+
+#[test]
+fn strip_strategies_keep_one_side() {
+  let rectangles = dims(&[(5, 3), (2, 9), (7, 7), (1, 1), (4, 6)]);
+  let options = PackOptions { bin_size: Dimension::new(16, 64), atlas_compact_steps: 8, growth: GrowthStrategy::HeightOnly, ..Default::default() };
+  for result in &pack(&rectangles, &options).unwrap() {
+    assert!(result.bins.iter().all(|bin| bin.size.w == 16));
+  }
+
+  let options = PackOptions { growth: GrowthStrategy::WidthOnly, ..options };
+  for result in &pack(&rectangles, &options).unwrap() {
+    assert!(result.bins.iter().all(|bin| bin.size.h == 64));
+  }
+}
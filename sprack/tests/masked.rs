@@ -0,0 +1,83 @@
+extern crate proptest;
+extern crate sprack;
+
+use proptest::prelude::*;
+use sprack::*;
+
+// pixel opacity from a seed, so that masks have some structure instead of pure noise
+fn pattern(seed: u32) -> impl Fn(u32, u32) -> bool {
+  move |x, y| ((x / 3).wrapping_add(seed).wrapping_mul(2654435761) ^ (y / 2).wrapping_mul(40503)) & 0b100 == 0
+}
+
+fn masks(max_side: u32, max_count: usize) -> impl Strategy<Value = (u32, Vec<Mask>)> {
+  (1u32..=6).prop_flat_map(move |cell| {
+    let mask = (1..=max_side, 1..=max_side, any::<u32>())
+      .prop_map(move |(w, h, seed)| Mask::from_fn(&Dimension::new(w, h), cell, pattern(seed)));
+    (Just(cell), prop::collection::vec(mask, 0..max_count))
+  })
+}
+
+fn mode() -> impl Strategy<Value = PackMode> {
+  prop_oneof![Just(PackMode::Atlas), Just(PackMode::Strip), Just(PackMode::TextureArray { max_layers: u32::MAX })]
+}
+
+proptest! {
+  #[test]
+  fn masked_packs_are_valid((_, masks) in masks(40, 32), flipping in any::<bool>(), mode in mode()) {
+    let options = PackOptions { bin_size: Dimension::new(40, 40), flipping, mode, ..Default::default() };
+    for result in &pack_masked(&masks, &options).unwrap() {
+      prop_assert_eq!(validate_masked(result, &masks, &options), Ok(()));
+    }
+  }
+
+  #[test]
+  fn rotated_masks_cover_rotated_pixels(w in 1u32..=30, h in 1u32..=30, cell in 1u32..=7, seed in any::<u32>()) {
+    let opaque = pattern(seed);
+    let mask = Mask::from_fn(&Dimension::new(w, h), cell, &opaque);
+    // rotated by 90deg counter-clockwise, pixel (x, y) comes from (w - 1 - y, x)
+    let exact = Mask::from_fn(&Dimension::new(h, w), cell, |x, y| opaque(w - 1 - y, x));
+    let rotated = mask.rotated();
+
+    prop_assert_eq!(rotated.size(), exact.size());
+    for row in 0..exact.rows() {
+      for col in 0..exact.cols() {
+        prop_assert!(rotated.is_opaque(col, row) || !exact.is_opaque(col, row));
+      }
+    }
+    if w % cell == 0 { prop_assert_eq!(rotated, exact); }
+  }
+}
+
+#[test]
+fn transparent_parts_are_shared() {
+  // only the left column of each sprite is opaque
+  let mask = Mask::from_fn(&Dimension::new(2, 2), 1, |x, _| x == 0);
+  let masks = [mask.clone(), mask];
+  let options = PackOptions { bin_size: Dimension::new(3, 2), ..Default::default() };
+
+  let rectangles = masks.iter().map(Mask::size).collect::<Vec<_>>();
+  assert!(pack(&rectangles, &options).unwrap().iter().all(|r| r.bins.len() == 2));
+
+  for result in &pack_masked(&masks, &options).unwrap() {
+    assert_eq!(result.bins.len(), 1);
+    assert_eq!(validate_masked(result, &masks, &options), Ok(()));
+    assert_eq!(validate(result, &rectangles, &options), Err(LayoutErr::Overlap { bin: 0, ids: (0, 1) }));
+  }
+}
+
+#[test]
+fn masked_bins_take_no_more_pieces() {
+  let masks = [Mask::new(&Dimension::new(4, 4), 2)];
+  let options = PackOptions { bin_size: Dimension::new(16, 16), ..Default::default() };
+  let mut bin = pack_masked(&masks, &options).unwrap().remove(0).bins.remove(0);
+
+  assert!(!bin.can_fit(&Dimension::new(1, 1), false));
+  assert!(!bin.insert(&Dimension::new(1, 1), 1, false));
+  assert_eq!(bin.free_rects().count(), 0);
+}
+
+#[test]
+fn masks_must_share_cell_size() {
+  let masks = [Mask::new(&Dimension::new(4, 4), 2), Mask::new(&Dimension::new(4, 4), 4)];
+  assert!(pack_masked(&masks, &PackOptions::default()).is_err());
+}
@@ -0,0 +1,69 @@
+extern crate sprack;
+
+use sprack::*;
+use std::sync::{Arc, Mutex};
+
+fn rectangles() -> Vec<Dimension> {
+  (1..=40).map(|i| Dimension::new(i % 7 + 1, i % 5 + 1)).collect()
+}
+
+fn recording_options(records: &Arc<Mutex<Vec<Progress>>>) -> PackOptions {
+  let records = Arc::clone(records);
+  let progress = ProgressHook::new(move |p| records.lock().unwrap().push(p));
+  PackOptions { bin_size: Dimension::new(16, 16), atlas_compact_steps: 4, progress: Some(progress), ..Default::default() }
+}
+
+#[test]
+fn progress_reaches_totals() {
+  let records = Arc::new(Mutex::new(Vec::new()));
+  let options = recording_options(&records);
+  let rectangles = rectangles();
+  pack(&rectangles, &options).unwrap();
+
+  let records = records.lock().unwrap();
+  let heuristics = options.sort_heuristics.len();
+  assert_eq!(records.len(), rectangles.len() * heuristics + heuristics);
+  assert!(records.iter().all(|p| p.heuristics == heuristics && p.rectangles == rectangles.len() * heuristics));
+  assert_eq!(records.iter().map(|p| p.placed).max(), Some(rectangles.len() * heuristics));
+  assert_eq!(records.iter().map(|p| p.heuristics_done).max(), Some(heuristics));
+}
+
+#[test]
+fn masked_pack_reports_progress() {
+  let records = Arc::new(Mutex::new(Vec::new()));
+  let options = recording_options(&records);
+  let masks = rectangles().iter().map(|r| Mask::new(r, 2)).collect::<Vec<_>>();
+  pack_masked(&masks, &options).unwrap();
+
+  let heuristics = options.sort_heuristics.len();
+  assert_eq!(records.lock().unwrap().iter().map(|p| p.placed).max(), Some(masks.len() * heuristics));
+}
+
+#[test]
+fn cancelled_pack_fails() {
+  let cancel = CancelToken::new();
+  cancel.cancel();
+  let options = PackOptions { cancel: Some(cancel), ..Default::default() };
+  assert!(pack(&rectangles(), &options).is_err());
+}
+
+#[test]
+fn pack_is_cancelled_from_progress_hook() {
+  for &mode in &[PackMode::Atlas, PackMode::Strip] {
+    let cancel = CancelToken::new();
+    let placed = Arc::new(Mutex::new(0));
+    let (token, counter) = (cancel.clone(), Arc::clone(&placed));
+    let progress = ProgressHook::new(move |p| {
+      *counter.lock().unwrap() = p.placed;
+      if p.placed >= 10 { token.cancel(); }
+    });
+
+    let options = PackOptions { progress: Some(progress), cancel: Some(cancel), mode, sort_heuristics: vec![heuristic_by_name("area").unwrap()], ..Default::default() };
+    match pack(&rectangles(), &options) {
+      Err(PackErr(message)) => assert_eq!(message, "Cancelled"),
+      Ok(_) => panic!("pack was not cancelled"),
+    }
+    // the last report comes from the finished heuristic
+    assert_eq!(*placed.lock().unwrap(), 10);
+  }
+}
@@ -0,0 +1,186 @@
+// run with `--features serde`
+#![cfg(feature = "serde")]
+
+extern crate serde_json;
+extern crate sprack;
+
+use sprack::*;
+
+fn sample_result() -> PackResult {
+  let rectangles = [Dimension::new(20, 10), Dimension::new(8, 8), Dimension::new(5, 12), Dimension::new(16, 3)];
+  let options = PackOptions { bin_size: Dimension::new(64, 64), ..Default::default() };
+  pack(&rectangles, &options).unwrap().remove(0)
+}
+
+fn free_area(bin: &Bin) -> u64 {
+  bin.free_rects().map(|r| r.size.area()).sum()
+}
+
+fn placed_area(bin: &Bin) -> u64 {
+  bin.placements.iter().map(|p| p.rect.size.area()).sum()
+}
+
+#[test]
+fn pack_result_round_trip() {
+  let result = sample_result();
+  let json = serde_json::to_string(&result).unwrap();
+  let loaded: PackResult = serde_json::from_str(&json).unwrap();
+
+  assert_eq!(loaded.heuristics.name(), result.heuristics.name());
+  assert_eq!(loaded.bins.len(), result.bins.len());
+  for (loaded, bin) in loaded.bins.iter().zip(&result.bins) {
+    assert_eq!(loaded.size, bin.size);
+    assert_eq!(loaded.placements, bin.placements);
+  }
+}
+
+#[test]
+fn pack_options_round_trip() {
+  let options = PackOptions {
+    bin_size: Dimension::new(300, 200),
+    atlas_compact_steps: 3,
+    mode: PackMode::TextureArray { max_layers: 4 },
+    flipping: true,
+    sort_heuristics: vec![heuristic_by_name("area").unwrap(), heuristic_by_name("height").unwrap()],
+    ..Default::default()
+  };
+  let json = serde_json::to_string(&options).unwrap();
+  let loaded: PackOptions = serde_json::from_str(&json).unwrap();
+
+  assert_eq!(loaded.bin_size, options.bin_size);
+  assert_eq!(loaded.atlas_compact_steps, options.atlas_compact_steps);
+  assert_eq!(loaded.mode, options.mode);
+  assert_eq!(loaded.flipping, options.flipping);
+  let names = |o: &PackOptions| o.sort_heuristics.iter().map(|h| h.name()).collect::<Vec<_>>();
+  assert_eq!(names(&loaded), names(&options));
+}
+
+#[test]
+fn bin_serializes_size_and_placements_only() {
+  let bin = sample_result().bins.remove(0);
+  let value = serde_json::to_value(&bin).unwrap();
+  let mut keys = value.as_object().unwrap().keys().cloned().collect::<Vec<_>>();
+  keys.sort();
+  assert_eq!(keys, ["placements", "size"]);
+}
+
+#[test]
+fn loaded_bin_keeps_its_free_space() {
+  let bin = sample_result().bins.remove(0);
+  let mut loaded: Bin = serde_json::from_str(&serde_json::to_string(&bin).unwrap()).unwrap();
+
+  // packer layouts are guillotine cuts, so all space around placements comes back
+  assert_eq!(free_area(&loaded) + placed_area(&loaded), bin.size.area());
+  assert!(loaded.can_fit(&Dimension::new(20, 40), false));
+  assert!(loaded.insert(&Dimension::new(20, 40), 9, false));
+
+  let added = loaded.placements.last().unwrap().rect;
+  assert!(added.r() <= loaded.size.w && added.b() <= loaded.size.h);
+  let overlaps = |p: &Placement| p.rect.l() < added.r() && added.l() < p.rect.r() && p.rect.t() < added.b() && added.t() < p.rect.b();
+  assert!(!bin.placements.iter().any(overlaps));
+}
+
+#[test]
+fn loaded_full_bin_rejects_inserts() {
+  let json = r#"{"size":{"w":4,"h":2},"placements":[
+    {"index":0,"rect":{"x":0,"y":0,"size":{"w":2,"h":2},"flipped":false}},
+    {"index":1,"rect":{"x":2,"y":0,"size":{"w":2,"h":2},"flipped":true}}]}"#;
+  let mut bin: Bin = serde_json::from_str(json).unwrap();
+  assert_eq!(bin.free_rects().count(), 0);
+  assert!(!bin.insert(&Dimension::new(1, 1), 2, false));
+}
+
+#[test]
+fn internal_state_in_input_is_ignored() {
+  // layout of older versions, which also wrote out the node tree
+  let json = r#"{"size":{"w":8,"h":8},"placements":[
+    {"index":0,"rect":{"x":0,"y":0,"size":{"w":4,"h":8},"flipped":false}}],
+    "tree":{"nodes":[{"id":null,"bounds":{"x":0,"y":0,"size":{"w":8,"h":8},"flipped":false},"children":[5,6],"parent":null,"free":{"w":8,"h":8}}]},
+    "last_rejected_size":{"w":8,"h":8}}"#;
+  let mut bin: Bin = serde_json::from_str(json).unwrap();
+  assert!(bin.insert(&Dimension::new(4, 8), 1, false));
+  assert_eq!(bin.placements[1].rect, Rectangle { x: 4, y: 0, size: Dimension::new(4, 8), flipped: false });
+}
+
+#[test]
+fn invalid_bins_are_rejected() {
+  let bin = |placements: &str| format!(r#"{{"size":{{"w":8,"h":8}},"placements":[{}]}}"#, placements);
+  let placement = |index: u32, x: u32, y: u32, w: u32, h: u32| {
+    format!(r#"{{"index":{},"rect":{{"x":{},"y":{},"size":{{"w":{},"h":{}}},"flipped":false}}}}"#, index, x, y, w, h)
+  };
+
+  let overlapping = bin(&format!("{},{}", placement(0, 0, 0, 5, 5), placement(1, 4, 4, 4, 4)));
+  assert!(serde_json::from_str::<Bin>(&overlapping).unwrap_err().to_string().contains("Placements overlap"));
+
+  let outside = bin(&placement(0, 6, 0, 4, 4));
+  assert!(serde_json::from_str::<Bin>(&outside).unwrap_err().to_string().contains("outside of the bin"));
+
+  let overflowing = bin(&placement(0, 4_294_967_295, 0, 4, 4));
+  assert!(serde_json::from_str::<Bin>(&overflowing).is_err());
+
+  let empty = bin(&placement(0, 0, 0, 0, 4));
+  assert!(serde_json::from_str::<Bin>(&empty).is_err());
+}
+
+#[test]
+fn non_guillotine_layout_keeps_outer_space() {
+  // pinwheel around the center cell, no straight cut separates these four
+  let json = r#"{"size":{"w":5,"h":5},"placements":[
+    {"index":0,"rect":{"x":0,"y":0,"size":{"w":2,"h":1},"flipped":false}},
+    {"index":1,"rect":{"x":2,"y":0,"size":{"w":1,"h":2},"flipped":false}},
+    {"index":2,"rect":{"x":1,"y":2,"size":{"w":2,"h":1},"flipped":false}},
+    {"index":3,"rect":{"x":0,"y":1,"size":{"w":1,"h":2},"flipped":false}}]}"#;
+  let mut bin: Bin = serde_json::from_str(json).unwrap();
+  assert!(bin.insert(&Dimension::new(2, 5), 4, false));
+  assert!(bin.insert(&Dimension::new(3, 2), 5, false));
+  assert!(!bin.insert(&Dimension::new(1, 1), 6, false));
+}
+
+#[test]
+fn masked_result_round_trip() {
+  // only the top left quarter is opaque, so the second box overlaps the first one
+  let mask = Mask::from_fn(&Dimension::new(4, 4), 1, |x, y| x < 2 && y < 2);
+  let masks = [mask.clone(), mask];
+  let options = PackOptions { bin_size: Dimension::new(6, 4), ..Default::default() };
+  let result = pack_masked(&masks, &options).unwrap().remove(0);
+  let bin = &result.bins[0];
+  let (a, b) = (bin.placements[0].rect, bin.placements[1].rect);
+  assert!(a.l() < b.r() && b.l() < a.r() && a.t() < b.b() && b.t() < a.b());
+
+  let json = serde_json::to_string(&result).unwrap();
+  let mut loaded: PackResult = serde_json::from_str(&json).unwrap();
+  assert_eq!(loaded.bins[0].placements, bin.placements);
+  assert_eq!(validate_masked(&loaded, &masks, &options), Ok(()));
+  // the same as packed, no free space is left to insert into
+  assert!(!loaded.bins[0].insert(&Dimension::new(1, 1), 2, false));
+  assert_eq!(serde_json::to_string(&loaded).unwrap(), json);
+}
+
+#[test]
+fn masked_bins_are_still_bounded() {
+  let json = r#"{"size":{"w":4,"h":4},"masked":true,"placements":[
+    {"index":0,"rect":{"x":2,"y":0,"size":{"w":4,"h":4},"flipped":false}}]}"#;
+  assert!(serde_json::from_str::<Bin>(json).unwrap_err().to_string().contains("outside of the bin"));
+}
+
+#[test]
+fn mask_round_trip() {
+  let mask = Mask::from_fn(&Dimension::new(5, 3), 2, |x, y| x == y);
+  let loaded: Mask = serde_json::from_str(&serde_json::to_string(&mask).unwrap()).unwrap();
+  assert_eq!(loaded, mask);
+}
+
+#[test]
+fn inconsistent_masks_are_rejected() {
+  let mask = |cell: u32, cols: u32, rows: u32, cells: usize| {
+    let cells = vec!["true"; cells].join(",");
+    format!(r#"{{"size":{{"w":5,"h":3}},"cell":{},"cols":{},"rows":{},"cells":[{}]}}"#, cell, cols, rows, cells)
+  };
+  assert!(serde_json::from_str::<Mask>(&mask(2, 3, 2, 6)).is_ok());
+
+  let error = |json: String| serde_json::from_str::<Mask>(&json).unwrap_err().to_string();
+  assert!(error(mask(0, 3, 2, 6)).contains("cell size must not be zero"));
+  assert!(error(mask(2, 2, 2, 4)).contains("do not match its size and cell"));
+  assert!(error(mask(2, 3, 2, 5)).contains("cells do not match"));
+  assert!(error(mask(2, 3, 2, 7)).contains("cells do not match"));
+}
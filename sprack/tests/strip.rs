@@ -0,0 +1,65 @@
+extern crate proptest;
+extern crate sprack;
+
+use proptest::prelude::*;
+use sprack::*;
+
+fn strip_options(width: u32, flipping: bool) -> PackOptions {
+  PackOptions { bin_size: Dimension::new(width, 0), mode: PackMode::Strip, flipping, ..Default::default() }
+}
+
+proptest! {
+  #[test]
+  fn strips_are_valid(width in 1u32..=96, sides in prop::collection::vec((1u32..=96, 1u32..=96), 0..48), flipping in any::<bool>()) {
+    // pieces wider than the strip are only allowed if they can be rotated
+    let rectangles = sides.iter()
+      .map(|&(w, h)| if flipping && w > width && h <= width { Dimension::new(w, h) } else { Dimension::new((w - 1) % width + 1, h) })
+      .collect::<Vec<_>>();
+    let options = strip_options(width, flipping);
+
+    for result in &pack(&rectangles, &options).unwrap() {
+      prop_assert_eq!(validate(result, &rectangles, &options), Ok(()));
+      prop_assert_eq!(result.bins.len(), 1);
+
+      let bin = &result.bins[0];
+      let height = bin.placements.iter().map(|p| p.rect.b()).max().unwrap_or(1);
+      prop_assert_eq!(bin.size, Dimension::new(width, height));
+      for free in bin.free_rects() {
+        prop_assert!(free.r() <= bin.size.w && free.b() <= bin.size.h, "{:?} outside of {:?}", free, bin.size);
+      }
+    }
+  }
+}
+
+#[test]
+fn strip_ignores_bin_height() {
+  let rectangles = [Dimension::new(4, 300), Dimension::new(4, 200), Dimension::new(8, 50)];
+  let options = PackOptions { atlas_compact_steps: 4, ..strip_options(8, false) };
+  let results = pack(&rectangles, &options).unwrap();
+  for result in &results {
+    assert_eq!(validate(result, &rectangles, &options), Ok(()));
+    assert_eq!(result.bins.len(), 1);
+    assert_eq!(result.bins[0].size.w, 8);
+  }
+  assert_eq!(results.iter().map(|r| r.bins[0].size.h).min(), Some(350));
+}
+
+#[test]
+fn strip_rejects_pieces_wider_than_strip() {
+  let rectangles = [Dimension::new(9, 1)];
+  assert!(pack(&rectangles, &strip_options(8, false)).is_err());
+  assert!(pack(&rectangles, &strip_options(8, true)).is_ok());
+  assert!(pack(&rectangles, &strip_options(0, true)).is_err());
+}
+
+#[test]
+fn cropped_bin_keeps_inserts_inside() {
+  let mut bin = Bin::new(&Dimension::new(10, 100));
+  assert!(bin.insert(&Dimension::new(6, 4), 0, false));
+  assert!(!bin.crop(Dimension::new(10, 3)));
+  assert!(bin.crop(Dimension::new(10, 4)));
+
+  assert!(bin.insert(&Dimension::new(4, 4), 1, false));
+  assert!(!bin.insert(&Dimension::new(1, 1), 2, false));
+  assert!(bin.free_rects().next().is_none());
+}
@@ -0,0 +1,56 @@
+extern crate proptest;
+extern crate sprack;
+
+use proptest::prelude::*;
+use sprack::*;
+
+fn array_options(bin_size: Dimension, max_layers: u32) -> PackOptions {
+  PackOptions { bin_size, mode: PackMode::TextureArray { max_layers }, atlas_compact_steps: 8, ..Default::default() }
+}
+
+proptest! {
+  #[test]
+  fn layers_are_valid(side in 1u32..=64, sides in prop::collection::vec((1u32..=64, 1u32..=64), 0..48), flipping in any::<bool>()) {
+    let rectangles = sides.iter().map(|&(w, h)| Dimension::new((w - 1) % side + 1, (h - 1) % side + 1)).collect::<Vec<_>>();
+    let options = PackOptions { flipping, ..array_options(Dimension::new(side, side), u32::MAX) };
+
+    let results = pack(&rectangles, &options).unwrap();
+    prop_assert_eq!(results.len(), options.sort_heuristics.len());
+    for pair in results.windows(2) {
+      prop_assert!(pair[0].layer_count() <= pair[1].layer_count());
+    }
+    for result in &results {
+      prop_assert_eq!(validate(result, &rectangles, &options), Ok(()));
+      for (layer, placement) in result.layered_placements() {
+        prop_assert!(result.bins[layer].placements.contains(placement));
+      }
+    }
+  }
+}
+
+#[test]
+fn layer_limit_is_enforced() {
+  let rectangles = vec![Dimension::new(8, 8); 5];
+  let bin_size = Dimension::new(16, 16);
+
+  assert!(pack(&rectangles, &array_options(bin_size, 1)).is_err());
+  assert!(pack(&rectangles, &array_options(bin_size, 0)).is_err());
+
+  let options = array_options(bin_size, 2);
+  for result in &pack(&rectangles, &options).unwrap() {
+    assert_eq!(result.layer_count(), 2);
+    assert_eq!(validate(result, &rectangles, &options), Ok(()));
+    assert_eq!(validate(result, &rectangles, &array_options(bin_size, 1)), Err(LayoutErr::TooManyLayers));
+  }
+}
+
+#[test]
+fn layers_keep_full_size() {
+  let rectangles = [Dimension::new(2, 2)];
+  let options = array_options(Dimension::new(16, 16), 1);
+  let mut result = pack(&rectangles, &options).unwrap().remove(0);
+  assert_eq!(result.bins[0].size, Dimension::new(16, 16));
+
+  result.bins[0].size = Dimension::new(8, 8);
+  assert_eq!(validate(&result, &rectangles, &options), Err(LayoutErr::LayerSize { bin: 0 }));
+}
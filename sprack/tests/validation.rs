@@ -0,0 +1,123 @@
+extern crate proptest;
+extern crate sprack;
+
+use proptest::prelude::*;
+use sprack::*;
+
+fn bin_and_rectangles(max_side: u32, max_count: usize) -> impl Strategy<Value = (Dimension, Vec<Dimension>)> {
+  (1..=max_side, 1..=max_side).prop_flat_map(move |(w, h)| {
+    let rect = (1..=w, 1..=h).prop_map(|(w, h)| Dimension::new(w, h));
+    (Just(Dimension::new(w, h)), prop::collection::vec(rect, 0..max_count))
+  })
+}
+
+fn check_all_heuristics(rectangles: &[Dimension], options: &PackOptions) -> Result<(), TestCaseError> {
+  let results = pack(rectangles, options).unwrap();
+  prop_assert_eq!(results.len(), options.sort_heuristics.len());
+  for result in &results {
+    prop_assert_eq!(validate(result, rectangles, options), Ok(()), "heuristic {:?}", result.heuristics);
+  }
+  Ok(())
+}
+
+fn growth_strategy() -> impl Strategy<Value = GrowthStrategy> {
+  prop::sample::select(GrowthStrategy::ALL.to_vec())
+}
+
+proptest! {
+  #[test]
+  fn packs_are_valid((bin_size, rectangles) in bin_and_rectangles(128, 64), flipping in any::<bool>(), steps in 0u8..=8, growth in growth_strategy()) {
+    let options = PackOptions { bin_size, flipping, atlas_compact_steps: steps, growth, ..Default::default() };
+    check_all_heuristics(&rectangles, &options)?;
+  }
+
+  // tiny bins with many growth steps run into `Bin::resize` failures all the time
+  #[test]
+  fn packs_with_failing_resizes_are_valid((bin_size, rectangles) in bin_and_rectangles(12, 24), flipping in any::<bool>(), steps in 1u8..=255, growth in growth_strategy()) {
+    let options = PackOptions { bin_size, flipping, atlas_compact_steps: steps, growth, ..Default::default() };
+    check_all_heuristics(&rectangles, &options)?;
+  }
+
+  #[test]
+  fn packs_of_rotated_pieces_are_valid(w in 1u32..=64, h in 1u32..=64, sides in prop::collection::vec((1u32..=64, 1u32..=64), 0..32)) {
+    let (short, long) = if w < h { (w, h) } else { (h, w) };
+    // every piece fits the bin only if it may be rotated
+    let rectangles = sides.iter().map(|&(a, b)| Dimension::new(a % short + 1, b % long + 1)).collect::<Vec<_>>();
+    let options = PackOptions { bin_size: Dimension::new(w, h), flipping: true, ..Default::default() };
+    check_all_heuristics(&rectangles, &options)?;
+  }
+}
+
+#[test]
+fn failed_resize_keeps_bin_intact() {
+  let rectangles = [Dimension::new(3, 1), Dimension::new(4, 4), Dimension::new(2, 6)];
+  let options = PackOptions { bin_size: Dimension::new(8, 9), ..Default::default() };
+  let mut bin = Bin::new(&Dimension::new(8, 8));
+  for (id, rect) in rectangles.iter().enumerate() {
+    assert!(bin.insert(rect, id as u32, false));
+  }
+  let before = bin.clone();
+
+  assert!(!bin.resize(options.bin_size, false));
+  assert_eq!(bin, before);
+  let result = PackResult { bins: vec![bin], heuristics: heuristic_by_name("area").unwrap() };
+  assert_eq!(validate(&result, &rectangles, &options), Ok(()));
+}
+
+#[test]
+fn huge_bins_are_valid() {
+  let side = u32::MAX;
+  let rectangles = [Dimension::new(side - 5, 7), Dimension::new(3, side), Dimension::new(side, side - 10), Dimension::new(1 << 31, 1 << 31)];
+  for &steps in &[0, 1, 255] {
+    for &growth in &GrowthStrategy::ALL {
+      let options = PackOptions { bin_size: Dimension::new(side, side), flipping: true, atlas_compact_steps: steps, growth, ..Default::default() };
+      for result in &pack(&rectangles, &options).unwrap() {
+        assert_eq!(validate(result, &rectangles, &options), Ok(()));
+      }
+    }
+  }
+}
+
+fn single_bin(size: Dimension, placements: &[(u32, u32, u32, u32, u32, bool)]) -> PackResult {
+  let mut bin = Bin::new(&size);
+  bin.placements = placements.iter()
+    .map(|&(index, x, y, w, h, flipped)| Placement { index, rect: Rectangle { x, y, size: Dimension::new(w, h), flipped } })
+    .collect();
+  PackResult { bins: vec![bin], heuristics: heuristic_by_name("area").unwrap() }
+}
+
+#[test]
+fn detects_broken_layouts() {
+  let rectangles = [Dimension::new(4, 2), Dimension::new(3, 3)];
+  let options = PackOptions { bin_size: Dimension::new(8, 8), ..Default::default() };
+  let size = options.bin_size;
+
+  let valid = single_bin(size, &[(0, 0, 0, 4, 2, false), (1, 4, 0, 3, 3, false)]);
+  assert_eq!(validate(&valid, &rectangles, &options), Ok(()));
+
+  let overlap = single_bin(size, &[(0, 0, 0, 4, 2, false), (1, 3, 1, 3, 3, false)]);
+  assert_eq!(validate(&overlap, &rectangles, &options), Err(LayoutErr::Overlap { bin: 0, ids: (0, 1) }));
+
+  let out_of_bounds = single_bin(size, &[(0, 0, 0, 4, 2, false), (1, 6, 0, 3, 3, false)]);
+  assert_eq!(validate(&out_of_bounds, &rectangles, &options), Err(LayoutErr::OutOfBounds { bin: 0, id: 1 }));
+
+  let missing = single_bin(size, &[(1, 0, 0, 3, 3, false)]);
+  assert_eq!(validate(&missing, &rectangles, &options), Err(LayoutErr::Missing(0)));
+
+  let duplicate = single_bin(size, &[(0, 0, 0, 4, 2, false), (0, 0, 4, 4, 2, false)]);
+  assert_eq!(validate(&duplicate, &rectangles, &options), Err(LayoutErr::Duplicate(0)));
+
+  let unknown = single_bin(size, &[(2, 0, 0, 4, 2, false)]);
+  assert_eq!(validate(&unknown, &rectangles, &options), Err(LayoutErr::UnknownId(2)));
+
+  let wrong_size = single_bin(size, &[(0, 0, 0, 4, 3, false), (1, 4, 0, 3, 3, false)]);
+  assert_eq!(validate(&wrong_size, &rectangles, &options), Err(LayoutErr::SizeMismatch { bin: 0, id: 0 }));
+
+  let flipped = single_bin(size, &[(0, 0, 0, 2, 4, true), (1, 4, 0, 3, 3, false)]);
+  assert_eq!(validate(&flipped, &rectangles, &options), Err(LayoutErr::UnexpectedFlip { bin: 0, id: 0 }));
+  let flipping_allowed = PackOptions { flipping: true, ..options.clone() };
+  assert_eq!(validate(&flipped, &rectangles, &flipping_allowed), Ok(()));
+
+  let oversized = single_bin(Dimension::new(9, 8), &[(0, 0, 0, 4, 2, false), (1, 4, 0, 3, 3, false)]);
+  assert_eq!(validate(&oversized, &rectangles, &options), Err(LayoutErr::OversizedBin { bin: 0 }));
+}
@@ -1,5 +1,6 @@
 #[macro_use]
 extern crate serde_derive;
+extern crate serde;
 extern crate image;
 extern crate rayon;
 extern crate docopt;
@@ -15,9 +16,7 @@ use rayon::prelude::*;
 use image::{DynamicImage, GenericImage};
 use docopt::Docopt;
 
-const PNG_EXT: &str = "png";
-
-const USAGE: &'static str = "
+const USAGE: &str = "
 Usage:  sprack [options] ([-w SIDE] [-h SIDE] | [-s SIDE]) [--help | <files>...]
 
 Options:
@@ -26,6 +25,12 @@ Options:
     -w, --width=SIDE            Atlas width [default: 1024].
     -h, --height=SIDE           Atlas height [default: 1024].
     -s, --size=SIDE             Atlas width and height.
+    --strip                     Pack into one atlas of atlas width and minimal height, atlas height
+                                and increments are ignored.
+    --layers=NUM                Pack into at most NUM layers of atlas size for a texture array,
+                                trim and increments are ignored.
+    -m, --mask-cell=PX          Pack by opaque areas split into PX sized cells instead of bounding
+                                boxes, sprites may overlap with transparent parts.
     -f, --flipping              Allow placement of sprites rotated by 90 degrees.
     -t, --trim                  Trim resulting images to minimal size.
     -i, --increments-count=NUM  Allows incremental atlas size growth. 0 means atlas starts at
@@ -33,6 +38,25 @@ Options:
                                 INC size and if sprite doesn't fit - grows by another INC, where
                                 INC = min(1, SIDE/(NUM+1)). Allowed values are 0..255, the higher
                                 the value - the more time packing will take [default: 0].
+    -g, --growth=MODE           How atlas grows with increments: uniform, shorter_side, alternate,
+                                double (powers of two from SIDE/2^NUM), height_only or
+                                width_only [default: uniform].
+    --format=LIST               Comma-separated metadata formats written next to atlas images:
+                                json (<page>.json), yaml (<page>.yaml), ron (<page>.ron),
+                                protobuf (atlas.pb, see schema/atlas.proto), texturepacker_hash
+                                (<page>.hash.json), texturepacker_array (<page>.array.json),
+                                libgdx (atlas.atlas), cocos2d (<page>.plist), starling
+                                (<page>.xml), godot (<sprite>.tres), unity (<page>.png.meta),
+                                bevy (<page>.layout.ron), bevy_json (<page>.layout.json), css
+                                (atlas.css), scss (atlas.scss), rust (sprites.rs), c (sprites.h)
+                                or tiled (atlas.tsx). Sprites named NAME@2x are used for NAME on
+                                retina screens in CSS. Godot, Unity, Bevy, CSS and Tiled formats
+                                can not be used with flipping [default: json].
+    --template=FILE             Also render metadata with a Handlebars template, the output is
+                                named after FILE without .hbs extension and must not replace
+                                atlas images or other formats. Templates get heuristic,
+                                rotation, pages (index, image, size, sprites) and sprites (name,
+                                page, image, x, y, w, h, rotated, original size and offset).
     -k, --keep-work-dir         Do not delete temporary files after work.
     -h, --help                  Show this help message.
 ";
@@ -42,6 +66,12 @@ struct Args {
   arg_files: Vec<String>,
   flag_out: String,
   flag_increments_count: u8,
+  flag_growth: String,
+  flag_strip: bool,
+  flag_layers: Option<u32>,
+  flag_mask_cell: Option<u32>,
+  flag_format: String,
+  flag_template: Option<String>,
   flag_width: u32,
   flag_height: u32,
   flag_size: Option<u32>,
@@ -49,7 +79,6 @@ struct Args {
   flag_flipping: bool,
   flag_keep_work_dir: bool,
   flag_recursive: bool,
-  flag_help: bool,
 }
 
 impl<'a> From<&'a Args> for RunOptions<'a> {
@@ -60,21 +89,64 @@ impl<'a> From<&'a Args> for RunOptions<'a> {
       Dimension { w: args.flag_width, h: args.flag_height }
     };
 
+    let growth = GrowthStrategy::from_name(&args.flag_growth).unwrap_or_else(|| {
+      eprintln!("Error: unknown growth mode {:?}", args.flag_growth);
+      std::process::exit(1)
+    });
+
+    let mode = match (args.flag_strip, args.flag_layers) {
+      (false, None) => PackMode::Atlas,
+      (true, None) => PackMode::Strip,
+      (false, Some(max_layers)) => PackMode::TextureArray { max_layers },
+      (true, Some(_)) => {
+        eprintln!("Error: --strip and --layers can not be used together");
+        std::process::exit(1)
+      }
+    };
+
+    let exporters = args.flag_format.split(',').map(str::trim).filter(|f| !f.is_empty())
+      .map(|name| exporter_by_name(name).unwrap_or_else(|| {
+        eprintln!("Error: unknown metadata format {:?}, expected one of {}", name, exporter_names().join(", "));
+        std::process::exit(1)
+      }))
+      .collect::<Vec<_>>();
+
+    let rotation = draw_rotation(&exporters, args.flag_flipping).unwrap_or_else(|e| {
+      eprintln!("Error: {}", e);
+      std::process::exit(1)
+    });
+    // masks are rotated the way sprites are drawn by default
+    if args.flag_mask_cell.is_some() && rotation != Rotation::CounterClockwise {
+      eprintln!("Error: --mask-cell with --flipping needs formats with counter-clockwise rotation");
+      std::process::exit(1)
+    }
+
+    let template = args.flag_template.as_ref().map(|path| TemplateExporter::new(Path::new(path)).unwrap_or_else(|e| {
+      eprintln!("Error: failed to load template {:?} - {}", path, e);
+      std::process::exit(1)
+    }));
+
     let pack_options = PackOptions {
       bin_size,
       flipping: args.flag_flipping,
-      trim: args.flag_trim,
+      // texture array layers must keep the same size
+      trim: args.flag_trim && args.flag_layers.is_none(),
       atlas_compact_steps: args.flag_increments_count,
+      growth,
+      mode,
       ..Default::default()
     };
 
     RunOptions {
       keep_work_dir: args.flag_keep_work_dir,
+      mask_cell: args.flag_mask_cell,
+      exporters,
+      template,
+      rotation,
       input_paths: args.arg_files.iter().map(|f| Path::new(f.as_str())).collect(),
       output_path: Path::new(args.flag_out.as_str()),
       recursive: args.flag_recursive,
       pack_options,
-      ..Default::default()
     }
   }
 }
@@ -85,26 +157,30 @@ fn main() {
     .unwrap_or_else(|e| e.exit());
 
   println!("Args: {:?}", args);
+  // exits on bad arguments, so nothing is left behind in the work dir yet
+  let options = RunOptions::from(&args);
   let work_dir = new_work_dir().expect("Failed to create work dir");
   println!("Work dir is {:?}", &work_dir);
 
-  let options = RunOptions::from(&args);
   for path in &options.input_paths {
     println!("> {:?}", path)
   }
 
-  let samples = options.input_paths.iter()
-    .filter(|path| is_supported_format(path))
-    .map(|path| image::open(path).unwrap())
-    .collect::<Vec<_>>();
+  let sprite_paths = options.input_paths.iter().cloned().filter(|path| is_supported_format(path)).collect::<Vec<_>>();
+  let samples = sprite_paths.iter().map(|path| image::open(path).unwrap()).collect::<Vec<_>>();
+  let names = sprite_names(&sprite_paths);
 
   let input = samples.iter().map(|s| Dimension { w: s.width(), h: s.height() }).collect::<Vec<_>>();
-  let solutions = pack(&input, &options.pack_options);
+  let solutions = match options.mask_cell {
+    Some(cell) => pack_masked(&alpha_masks(&samples, cell), &options.pack_options),
+    None => pack(&input, &options.pack_options),
+  };
 
   let best: Option<&PackResult> = match solutions {
     Ok(ref solutions) => solutions.par_iter()
-      .map(|pack_result| (pack_result, write_solution(pack_result, &samples, &options, &work_dir)))
-      .min_by_key(|tuple| tuple.1)
+      .map(|pack_result| (pack_result, write_solution(pack_result, &samples, &names, &options, &work_dir)))
+      // fewer layers of a texture array beat smaller files
+      .min_by_key(|tuple| (layers_used(tuple.0, &options), tuple.1))
       .map(|tuple| tuple.0),
     Err(e) => {
       eprintln!("Error: {:?}", e);
@@ -113,7 +189,7 @@ fn main() {
   };
 
   if let Some(best) = best {
-    let best_result_dir = Path::new(&work_dir).join(&best.heuristics.name());
+    let best_result_dir = Path::new(&work_dir).join(best.heuristics.name());
     match copy_result_to_out(&best_result_dir, &options) {
       Ok(size) => println!("Best results with {}, {} bytes", &best.heuristics.name(), size),
       Err(e) => eprintln!("Failed to copy results from {:?} to {:?} - {:?}", &best_result_dir, &options.output_path, e),
@@ -123,23 +199,37 @@ fn main() {
   if !&options.keep_work_dir { cleanup_work_dir(&work_dir); }
 }
 
+fn layers_used(solution: &PackResult, options: &RunOptions) -> usize {
+  match options.pack_options.mode {
+    PackMode::TextureArray { .. } => solution.layer_count(),
+    _ => 0,
+  }
+}
+
+fn alpha_masks(images: &[DynamicImage], cell: u32) -> Vec<Mask> {
+  images.iter()
+    .map(|img| Mask::from_fn(&Dimension::new(img.width(), img.height()), cell, |x, y| img.get_pixel(x, y).data[3] > 0))
+    .collect()
+}
+
 fn is_supported_format(path: &Path) -> bool {
   if let Some(ext) = path.extension().map(OsStr::to_string_lossy).map(|e| e.to_lowercase()) {
-    let ext = ext.as_str();
-    match ext {
-      "png" | "bmp" | "gif" | "jpg" | "jpeg" | "ico" | "tiff" | "webp" | "ppm" => true,
-      _ => false
-    }
+    matches!(ext.as_str(), "png" | "bmp" | "gif" | "jpg" | "jpeg" | "ico" | "tiff" | "webp" | "ppm")
   } else { false }
 }
 
-fn write_solution(solution: &PackResult, images: &[DynamicImage], options: &RunOptions, work_dir: &AsRef<Path>) -> u64 {
-  let dir = Path::new(&work_dir.as_ref()).join(&solution.heuristics.name());
+fn write_solution(solution: &PackResult, images: &[DynamicImage], names: &[String], options: &RunOptions, work_dir: &dyn AsRef<Path>) -> u64 {
+  let dir = Path::new(&work_dir.as_ref()).join(solution.heuristics.name());
   std::fs::remove_dir_all(&dir).unwrap_or(());
-  std::fs::create_dir_all(&dir).expect(format!("Failed to create dir {:?}", &dir).as_ref());
+  std::fs::create_dir_all(&dir).unwrap_or_else(|_| panic!("Failed to create dir {:?}", &dir));
   let mut size = 0;
   for (i, bin) in solution.bins.iter().enumerate() {
-    size += draw_bin(&dir.join(i.to_string()).with_extension(PNG_EXT), images, bin, options.pack_options.trim);
+    size += draw_bin(&dir.join(page_image(i)), images, bin, options.pack_options.trim, options.mask_cell.is_some(), options.rotation);
+  }
+  let meta = AtlasMeta::new(solution, names, options);
+  for exporter in options.all_exporters() {
+    exporter.export(&meta, &dir)
+      .unwrap_or_else(|e| panic!("Failed to write {} metadata to {:?} - {:?}", exporter.name(), &dir, e));
   }
   println!("Heuristic {}: {} bins used, total size: {}b", solution.heuristics.name(), solution.bins.len(), size);
   size
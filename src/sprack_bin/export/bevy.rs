@@ -0,0 +1,101 @@
+use super::Exporter;
+use super::document::write_json;
+use sprack_bin::meta::AtlasMeta;
+
+use std::fs::File;
+use std::io::{BufWriter, Write, Result};
+use std::path::Path;
+
+// Bevy `TextureAtlasLayout` per page, `<page>.layout.ron` or `<page>.layout.json`.
+// Textures are indexed in placement order, RON names them in comments
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BevyExporter { Ron, Json }
+
+#[derive(Serialize)]
+struct Layout {
+  size: (u32, u32),
+  textures: Vec<URect>,
+}
+
+#[derive(Serialize)]
+struct URect {
+  min: (u32, u32),
+  max: (u32, u32),
+}
+
+impl Exporter for BevyExporter {
+  fn name(&self) -> &'static str {
+    match *self {
+      BevyExporter::Ron => "bevy",
+      BevyExporter::Json => "bevy_json",
+    }
+  }
+
+  fn supports_rotation(&self) -> bool { false }
+
+  fn export(&self, meta: &AtlasMeta, dir: &Path) -> Result<()> {
+    for page in &meta.pages {
+      match *self {
+        BevyExporter::Ron => {
+          let mut out = BufWriter::new(File::create(dir.join(&page.image).with_extension("layout.ron"))?);
+          writeln!(out, "(")?;
+          writeln!(out, "  size: ({}, {}),", page.size.w, page.size.h)?;
+          writeln!(out, "  textures: [")?;
+          for s in &page.sprites {
+            writeln!(out, "    (min: ({}, {}), max: ({}, {})), // {}", s.x, s.y, s.x + s.w, s.y + s.h, s.name)?;
+          }
+          writeln!(out, "  ],")?;
+          writeln!(out, ")")?;
+          out.flush()?;
+        }
+        BevyExporter::Json => {
+          let textures = page.sprites.iter().map(|s| URect { min: (s.x, s.y), max: (s.x + s.w, s.y + s.h) }).collect();
+          let layout = Layout { size: (page.size.w, page.size.h), textures };
+          write_json(&dir.join(&page.image).with_extension("layout.json"), &layout)?;
+        }
+      }
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  extern crate serde_json;
+
+  use super::*;
+  use super::super::draw_rotation;
+  use self::serde_json::{Value, from_str, json};
+  use sprack_bin::meta::Rotation;
+  use sprack_bin::testing::{WorkDir, atlas, page, sprite};
+
+  fn export(exporter: BevyExporter) -> WorkDir {
+    let meta = atlas(Rotation::CounterClockwise, vec![
+      page(0, (48, 80), vec![sprite("hero", 0, (10, 20, 12, 30), false), sprite("coin", 0, (0, 0, 8, 8), false)]),
+    ]);
+    let dir = WorkDir::new();
+    exporter.export(&meta, dir.path()).unwrap();
+    dir
+  }
+
+  #[test]
+  fn ron_layout_has_min_and_max_corners() {
+    let text = export(BevyExporter::Ron).read("0.layout.ron");
+    assert_eq!(text, "(\n  size: (48, 80),\n  textures: [\n    (min: (10, 20), max: (22, 50)), // hero\n    (min: (0, 0), max: (8, 8)), // coin\n  ],\n)\n");
+  }
+
+  #[test]
+  fn json_layout_has_min_and_max_corners() {
+    let layout: Value = from_str(&export(BevyExporter::Json).read("0.layout.json")).unwrap();
+    assert_eq!(layout, json!({
+      "size": [48, 80],
+      "textures": [{ "min": [10, 20], "max": [22, 50] }, { "min": [0, 0], "max": [8, 8] }],
+    }));
+  }
+
+  #[test]
+  fn rotated_sprites_are_refused() {
+    assert!(draw_rotation(&[&BevyExporter::Ron], true).is_err());
+    assert!(draw_rotation(&[&BevyExporter::Json], true).is_err());
+  }
+}
@@ -0,0 +1,119 @@
+use super::{Exporter, escape_xml};
+use sprack_bin::meta::{AtlasMeta, Rotation};
+
+use std::fs::File;
+use std::io::{BufWriter, Write, Result};
+use std::path::Path;
+
+// Cocos2d-x sprite frames plist (format 3), one `<page>.plist` per page.
+// `textureRect` keeps the unrotated size and rotated frames are turned clockwise
+pub struct Cocos2dExporter;
+
+impl Exporter for Cocos2dExporter {
+  fn name(&self) -> &'static str { "cocos2d" }
+
+  fn rotation(&self) -> Option<Rotation> { Some(Rotation::Clockwise) }
+
+  fn export(&self, meta: &AtlasMeta, dir: &Path) -> Result<()> {
+    for page in &meta.pages {
+      let mut out = BufWriter::new(File::create(dir.join(&page.image).with_extension("plist"))?);
+      writeln!(out, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+      writeln!(out, r#"<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">"#)?;
+      writeln!(out, r#"<plist version="1.0">"#)?;
+      writeln!(out, "  <dict>")?;
+      writeln!(out, "    <key>frames</key>")?;
+      writeln!(out, "    <dict>")?;
+      for sprite in &page.sprites {
+        let (w, h) = (sprite.original.w, sprite.original.h);
+        writeln!(out, "      <key>{}</key>", escape_xml(&sprite.name))?;
+        writeln!(out, "      <dict>")?;
+        writeln!(out, "        <key>aliases</key>")?;
+        writeln!(out, "        <array/>")?;
+        writeln!(out, "        <key>spriteOffset</key>")?;
+        writeln!(out, "        <string>{{0,0}}</string>")?;
+        writeln!(out, "        <key>spriteSize</key>")?;
+        writeln!(out, "        <string>{{{},{}}}</string>", w, h)?;
+        writeln!(out, "        <key>spriteSourceSize</key>")?;
+        writeln!(out, "        <string>{{{},{}}}</string>", w, h)?;
+        writeln!(out, "        <key>textureRect</key>")?;
+        writeln!(out, "        <string>{{{{{},{}}},{{{},{}}}}}</string>", sprite.x, sprite.y, w, h)?;
+        writeln!(out, "        <key>textureRotated</key>")?;
+        writeln!(out, "        <{}/>", sprite.rotated)?;
+        writeln!(out, "      </dict>")?;
+      }
+      writeln!(out, "    </dict>")?;
+      writeln!(out, "    <key>metadata</key>")?;
+      writeln!(out, "    <dict>")?;
+      writeln!(out, "      <key>format</key>")?;
+      writeln!(out, "      <integer>3</integer>")?;
+      writeln!(out, "      <key>pixelFormat</key>")?;
+      writeln!(out, "      <string>RGBA8888</string>")?;
+      writeln!(out, "      <key>premultiplyAlpha</key>")?;
+      writeln!(out, "      <false/>")?;
+      writeln!(out, "      <key>realTextureFileName</key>")?;
+      writeln!(out, "      <string>{}</string>", escape_xml(&page.image))?;
+      writeln!(out, "      <key>size</key>")?;
+      writeln!(out, "      <string>{{{},{}}}</string>", page.size.w, page.size.h)?;
+      writeln!(out, "      <key>textureFileName</key>")?;
+      writeln!(out, "      <string>{}</string>", escape_xml(&page.image))?;
+      writeln!(out, "    </dict>")?;
+      writeln!(out, "  </dict>")?;
+      writeln!(out, "</plist>")?;
+      out.flush()?;
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use super::super::{LibGdxExporter, draw_rotation};
+  use sprack_bin::testing::{WorkDir, atlas, page, sprite};
+
+  #[test]
+  fn rotated_frames_keep_unrotated_rect() {
+    // `tree` takes 20x8 in the atlas, it is 8x20 unrotated
+    let meta = atlas(Rotation::Clockwise, vec![
+      page(0, (64, 32), vec![sprite("hero", 0, (0, 0, 16, 24), false), sprite("tree", 0, (16, 0, 20, 8), true)]),
+    ]);
+    let dir = WorkDir::new();
+    Cocos2dExporter.export(&meta, dir.path()).unwrap();
+
+    let plist = dir.read("0.plist");
+    assert!(plist.contains(concat!(
+      "      <key>tree</key>\n",
+      "      <dict>\n",
+      "        <key>aliases</key>\n",
+      "        <array/>\n",
+      "        <key>spriteOffset</key>\n",
+      "        <string>{0,0}</string>\n",
+      "        <key>spriteSize</key>\n",
+      "        <string>{8,20}</string>\n",
+      "        <key>spriteSourceSize</key>\n",
+      "        <string>{8,20}</string>\n",
+      "        <key>textureRect</key>\n",
+      "        <string>{{16,0},{8,20}}</string>\n",
+      "        <key>textureRotated</key>\n",
+      "        <true/>\n",
+      "      </dict>\n",
+    )), "{}", plist);
+    assert!(plist.contains("        <string>{{0,0},{16,24}}</string>\n        <key>textureRotated</key>\n        <false/>\n"), "{}", plist);
+    assert!(plist.contains("      <key>size</key>\n      <string>{64,32}</string>\n"), "{}", plist);
+    assert!(plist.contains("      <key>textureFileName</key>\n      <string>0.png</string>\n"), "{}", plist);
+  }
+
+  #[test]
+  fn names_are_escaped() {
+    let meta = atlas(Rotation::Clockwise, vec![page(0, (8, 8), vec![sprite("<a & 'b'>", 0, (0, 0, 8, 8), false)])]);
+    let dir = WorkDir::new();
+    Cocos2dExporter.export(&meta, dir.path()).unwrap();
+    assert!(dir.read("0.plist").contains("      <key>&lt;a &amp; &apos;b&apos;&gt;</key>\n"));
+  }
+
+  #[test]
+  fn sprites_are_drawn_clockwise() {
+    assert_eq!(draw_rotation(&[&Cocos2dExporter], true), Ok(Rotation::Clockwise));
+    assert!(draw_rotation(&[&Cocos2dExporter, &LibGdxExporter], true).is_err());
+  }
+}
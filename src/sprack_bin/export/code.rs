@@ -0,0 +1,182 @@
+use super::Exporter;
+use sprack_bin::meta::{AtlasMeta, PageMeta, SpriteMeta, Rotation, unique_name};
+
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{BufWriter, Write, Result};
+use std::path::Path;
+
+// constants for sprites known at compile time, a Rust module in `sprites.rs` or a C header in
+// `sprites.h`. Identifiers are upper-cased sprite names with anything but letters and digits
+// replaced by `_`, the same in both languages
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CodeExporter { Rust, C }
+
+struct Constant<'a> {
+  ident: String,
+  page: &'a PageMeta,
+  sprite: &'a SpriteMeta,
+}
+
+impl Exporter for CodeExporter {
+  fn name(&self) -> &'static str {
+    match *self {
+      CodeExporter::Rust => "rust",
+      CodeExporter::C => "c",
+    }
+  }
+
+  fn export(&self, meta: &AtlasMeta, dir: &Path) -> Result<()> {
+    let constants = constants(meta);
+    match *self {
+      CodeExporter::Rust => write_rust(meta, &constants, &dir.join("sprites.rs")),
+      CodeExporter::C => write_c(meta, &constants, &dir.join("sprites.h")),
+    }
+  }
+}
+
+fn write_rust(meta: &AtlasMeta, constants: &[Constant], path: &Path) -> Result<()> {
+  let mut out = BufWriter::new(File::create(path)?);
+  writeln!(out, "// generated by sprack from {} packing, do not edit", meta.heuristic)?;
+  writeln!(out)?;
+  writeln!(out, "#[derive(Clone, Copy, PartialEq, Debug)]")?;
+  writeln!(out, "pub struct Sprite {{")?;
+  writeln!(out, "    // index into `PAGES`")?;
+  writeln!(out, "    pub page: usize,")?;
+  writeln!(out, "    // pixels of the atlas image, origin at the top left corner")?;
+  writeln!(out, "    pub x: u32,")?;
+  writeln!(out, "    pub y: u32,")?;
+  writeln!(out, "    pub w: u32,")?;
+  writeln!(out, "    pub h: u32,")?;
+  writeln!(out, "    // the same area in texture coordinates")?;
+  writeln!(out, "    pub u0: f32,")?;
+  writeln!(out, "    pub v0: f32,")?;
+  writeln!(out, "    pub u1: f32,")?;
+  writeln!(out, "    pub v1: f32,")?;
+  writeln!(out, "    // {}", rotation_comment(meta.rotation))?;
+  writeln!(out, "    pub rotated: bool,")?;
+  writeln!(out, "}}")?;
+  writeln!(out)?;
+  let pages = meta.pages.iter().map(|p| format!("{:?}", p.image)).collect::<Vec<_>>();
+  writeln!(out, "pub const PAGES: [&str; {}] = [{}];", pages.len(), pages.join(", "))?;
+  writeln!(out)?;
+  for c in constants {
+    let (s, (u0, v0, u1, v1)) = (c.sprite, uv(c));
+    writeln!(out, "pub const {}: Sprite = Sprite {{ page: {}, x: {}, y: {}, w: {}, h: {}, u0: {:?}, v0: {:?}, u1: {:?}, v1: {:?}, rotated: {} }};",
+      c.ident, s.page, s.x, s.y, s.w, s.h, u0, v0, u1, v1, s.rotated)?;
+  }
+  writeln!(out)?;
+  writeln!(out, "pub const SPRITES: [(&str, Sprite); {}] = [", constants.len())?;
+  for c in constants {
+    writeln!(out, "    ({:?}, {}),", c.sprite.name, c.ident)?;
+  }
+  writeln!(out, "];")?;
+  out.flush()
+}
+
+fn write_c(meta: &AtlasMeta, constants: &[Constant], path: &Path) -> Result<()> {
+  let mut out = BufWriter::new(File::create(path)?);
+  writeln!(out, "/* generated by sprack from {} packing, do not edit */", meta.heuristic)?;
+  writeln!(out, "#ifndef SPRACK_SPRITES_H")?;
+  writeln!(out, "#define SPRACK_SPRITES_H")?;
+  writeln!(out)?;
+  writeln!(out, "typedef struct sprack_sprite {{")?;
+  writeln!(out, "  /* index into SPRACK_PAGES */")?;
+  writeln!(out, "  unsigned page;")?;
+  writeln!(out, "  /* pixels of the atlas image, origin at the top left corner */")?;
+  writeln!(out, "  unsigned x, y, w, h;")?;
+  writeln!(out, "  /* the same area in texture coordinates */")?;
+  writeln!(out, "  float u0, v0, u1, v1;")?;
+  writeln!(out, "  /* {} */", rotation_comment(meta.rotation))?;
+  writeln!(out, "  int rotated;")?;
+  writeln!(out, "}} sprack_sprite;")?;
+  writeln!(out)?;
+  writeln!(out, "#define SPRACK_PAGE_COUNT {}", meta.pages.len())?;
+  let pages = meta.pages.iter().map(|p| format!("{:?}", p.image)).collect::<Vec<_>>();
+  writeln!(out, "static const char *const SPRACK_PAGES[] = {{ {} }};", pages.join(", "))?;
+  writeln!(out)?;
+  for c in constants {
+    let (s, (u0, v0, u1, v1)) = (c.sprite, uv(c));
+    writeln!(out, "static const sprack_sprite {} = {{ {}, {}, {}, {}, {}, {:?}f, {:?}f, {:?}f, {:?}f, {} }};",
+      c.ident, s.page, s.x, s.y, s.w, s.h, u0, v0, u1, v1, s.rotated as u8)?;
+  }
+  writeln!(out)?;
+  writeln!(out, "#define SPRACK_SPRITE_COUNT {}", constants.len())?;
+  writeln!(out, "#endif")?;
+  out.flush()
+}
+
+fn rotation_comment(rotation: Rotation) -> &'static str {
+  match rotation {
+    Rotation::Clockwise => "turned by 90 degrees clockwise in the atlas, width and height are swapped",
+    Rotation::CounterClockwise => "turned by 90 degrees counter-clockwise in the atlas, width and height are swapped",
+  }
+}
+
+fn uv(c: &Constant) -> (f32, f32, f32, f32) {
+  let (w, h) = (c.page.size.w as f32, c.page.size.h as f32);
+  let s = c.sprite;
+  (s.x as f32 / w, s.y as f32 / h, (s.x + s.w) as f32 / w, (s.y + s.h) as f32 / h)
+}
+
+// different names may sanitize to the same identifier, later ones get a `_2`, `_3`... suffix.
+// Names of the generated tables are taken from the start
+fn constants(meta: &AtlasMeta) -> Vec<Constant<'_>> {
+  let mut taken = ["PAGES", "SPRITES"].iter().map(|t| t.to_string()).collect::<HashSet<_>>();
+  meta.pages.iter().flat_map(|page| page.sprites.iter().map(move |sprite| (page, sprite)))
+    .map(|(page, sprite)| {
+      let mut ident = sprite.name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_uppercase() } else { '_' })
+        .collect::<String>();
+      // identifiers can not start with a digit, `_` alone is not one in Rust
+      if !ident.starts_with(|c: char| c.is_ascii_alphabetic()) { ident.insert_str(0, "SPRITE_"); }
+      Constant { ident: unique_name(&mut taken, &ident), page, sprite }
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use sprack_bin::testing::{WorkDir, atlas, page, sprite};
+
+  fn meta(names: &[&str]) -> AtlasMeta {
+    let sprites = names.iter().enumerate().map(|(i, name)| sprite(name, 0, (i as u32 * 8, 0, 8, 4), false)).collect();
+    atlas(Rotation::CounterClockwise, vec![page(0, (64, 16), sprites)])
+  }
+
+  fn idents(names: &[&str]) -> Vec<String> {
+    constants(&meta(names)).into_iter().map(|c| c.ident).collect()
+  }
+
+  #[test]
+  fn names_are_sanitized() {
+    assert_eq!(idents(&["hero", "big tree", "ui/button.9", "Coin-Gold"]), ["HERO", "BIG_TREE", "UI_BUTTON_9", "COIN_GOLD"]);
+  }
+
+  #[test]
+  fn leading_non_letters_are_prefixed() {
+    assert_eq!(idents(&["1up", "_shadow", "-"]), ["SPRITE_1UP", "SPRITE__SHADOW", "SPRITE__"]);
+  }
+
+  #[test]
+  fn repeated_idents_get_suffix() {
+    assert_eq!(idents(&["a b", "a_b", "a-b", "A.B"]), ["A_B", "A_B_2", "A_B_3", "A_B_4"]);
+    // table names are reserved
+    assert_eq!(idents(&["pages", "sprites", "pages_2"]), ["PAGES_2", "SPRITES_2", "PAGES_2_2"]);
+  }
+
+  #[test]
+  fn both_languages_use_the_same_idents() {
+    let dir = WorkDir::new();
+    let meta = meta(&["1up", "hero"]);
+    CodeExporter::Rust.export(&meta, dir.path()).unwrap();
+    CodeExporter::C.export(&meta, dir.path()).unwrap();
+
+    let (rust, c) = (dir.read("sprites.rs"), dir.read("sprites.h"));
+    assert!(rust.contains("pub const SPRITE_1UP: Sprite = Sprite { page: 0, x: 0, y: 0, w: 8, h: 4, "), "{}", rust);
+    assert!(rust.contains("pub const HERO: Sprite = Sprite { page: 0, x: 8, "), "{}", rust);
+    assert!(c.contains("static const sprack_sprite SPRITE_1UP = { 0, 0, 0, 8, 4, "), "{}", c);
+    assert!(c.contains("static const sprack_sprite HERO = { 0, 8, 0, 8, 4, "), "{}", c);
+  }
+}
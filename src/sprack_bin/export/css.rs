@@ -0,0 +1,172 @@
+use super::Exporter;
+use sprack_bin::meta::{AtlasMeta, PageMeta, SpriteMeta, unique_name};
+
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{BufWriter, Write, Result};
+use std::path::Path;
+
+const RETINA_SUFFIX: &str = "@2x";
+const RETINA_QUERY: &str = "@media (-webkit-min-device-pixel-ratio: 2), (min-resolution: 192dpi)";
+
+// `.sprite-<name>` class per sprite in `atlas.css`, or a `$sprites` map with a `sprite` mixin in
+// `atlas.scss`. Sprites named `<name>@2x` are drawn at half size, replacing `<name>` on retina
+// screens when both are packed
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CssExporter { Css, Scss }
+
+impl Exporter for CssExporter {
+  fn name(&self) -> &'static str {
+    match *self {
+      CssExporter::Css => "css",
+      CssExporter::Scss => "scss",
+    }
+  }
+
+  fn supports_rotation(&self) -> bool { false }
+
+  fn export(&self, meta: &AtlasMeta, dir: &Path) -> Result<()> {
+    match *self {
+      CssExporter::Css => write_css(meta, &dir.join("atlas.css")),
+      CssExporter::Scss => write_scss(meta, &dir.join("atlas.scss")),
+    }
+  }
+}
+
+fn write_css(meta: &AtlasMeta, path: &Path) -> Result<()> {
+  let mut out = BufWriter::new(File::create(path)?);
+  let sprites = sprites(meta).collect::<Vec<_>>();
+  // a base is a regular sprite, so `a@2x@2x` does not override `a@2x` when `a` is packed too
+  let has_base = |name: &str| !name.ends_with(RETINA_SUFFIX) && sprites.iter().any(|&(_, s)| s.name == name);
+
+  let mut rules = Vec::new();
+  let mut retina = Vec::new();
+  for &(page, sprite) in &sprites {
+    match sprite.name.strip_suffix(RETINA_SUFFIX) {
+      Some(base) if has_base(base) => retina.push((base, page, sprite)),
+      Some(base) => rules.push((base, page, sprite, 2)),
+      None => rules.push((sprite.name.as_str(), page, sprite, 1)),
+    }
+  }
+
+  // different names may sanitize to the same class, later ones get a `_2`, `_3`... suffix.
+  // Retina overrides reuse the class of their regular sprite
+  let mut taken = HashSet::new();
+  let mut classes = HashMap::new();
+  for &(name, page, sprite, scale) in &rules {
+    let class = unique_name(&mut taken, &class_name(name));
+    write_rule(&mut out, &class, page, sprite, scale, false)?;
+    classes.entry(name).or_insert(class);
+  }
+  if !retina.is_empty() {
+    writeln!(out, "{} {{", RETINA_QUERY)?;
+    for (base, page, sprite) in retina {
+      write_rule(&mut out, &classes[base], page, sprite, 2, true)?;
+    }
+    writeln!(out, "}}")?;
+  }
+  out.flush()
+}
+
+// retina overrides keep the size of the regular sprite
+fn write_rule<W: Write>(out: &mut W, class: &str, page: &PageMeta, s: &SpriteMeta, scale: u32, retina: bool) -> Result<()> {
+  let indent = if retina { "  " } else { "" };
+  writeln!(out, "{}.sprite-{} {{", indent, class)?;
+  writeln!(out, "{}  background-image: url(\"{}\");", indent, page.image)?;
+  writeln!(out, "{}  background-position: {} {};", indent, offset(s.x, scale), offset(s.y, scale))?;
+  if scale != 1 {
+    writeln!(out, "{}  background-size: {} {};", indent, px(page.size.w, scale), px(page.size.h, scale))?;
+  }
+  if !retina {
+    writeln!(out, "{}  width: {};", indent, px(s.w, scale))?;
+    writeln!(out, "{}  height: {};", indent, px(s.h, scale))?;
+  }
+  writeln!(out, "{}}}", indent)
+}
+
+fn write_scss(meta: &AtlasMeta, path: &Path) -> Result<()> {
+  let mut out = BufWriter::new(File::create(path)?);
+  writeln!(out, "@use \"sass:math\";")?;
+  writeln!(out)?;
+  writeln!(out, "$sprites: (")?;
+  for (page, s) in sprites(meta) {
+    let scale = if s.name.ends_with(RETINA_SUFFIX) { 2 } else { 1 };
+    writeln!(out, "  \"{}\": (image: \"{}\", x: {}px, y: {}px, width: {}px, height: {}px, atlas-width: {}px, atlas-height: {}px, scale: {}),",
+      s.name.replace('\\', "\\\\").replace('"', "\\\""), page.image, s.x, s.y, s.w, s.h, page.size.w, page.size.h, scale)?;
+  }
+  writeln!(out, ");")?;
+  writeln!(out)?;
+  writeln!(out, "@mixin sprite($name) {{")?;
+  writeln!(out, "  $sprite: map-get($sprites, $name);")?;
+  writeln!(out, "  $scale: map-get($sprite, scale);")?;
+  writeln!(out, "  background-image: url(map-get($sprite, image));")?;
+  writeln!(out, "  background-position: math.div(-map-get($sprite, x), $scale) math.div(-map-get($sprite, y), $scale);")?;
+  writeln!(out, "  @if $scale != 1 {{")?;
+  writeln!(out, "    background-size: math.div(map-get($sprite, atlas-width), $scale) math.div(map-get($sprite, atlas-height), $scale);")?;
+  writeln!(out, "  }}")?;
+  writeln!(out, "  width: math.div(map-get($sprite, width), $scale);")?;
+  writeln!(out, "  height: math.div(map-get($sprite, height), $scale);")?;
+  writeln!(out, "}}")?;
+  out.flush()
+}
+
+fn sprites<'a>(meta: &'a AtlasMeta) -> impl Iterator<Item = (&'a PageMeta, &'a SpriteMeta)> + 'a {
+  meta.pages.iter().flat_map(|page| page.sprites.iter().map(move |s| (page, s)))
+}
+
+// letters, digits, `-` and `_` are kept, anything else becomes `_`
+fn class_name(name: &str) -> String {
+  name.chars().map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' }).collect()
+}
+
+fn px(value: u32, scale: u32) -> String {
+  format!("{}px", f64::from(value) / f64::from(scale))
+}
+
+fn offset(value: u32, scale: u32) -> String {
+  if value == 0 { "0".to_string() } else { format!("-{}", px(value, scale)) }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use sprack_bin::meta::Rotation;
+  use sprack_bin::testing::{WorkDir, atlas, page, sprite};
+
+  fn css(names: &[&str]) -> String {
+    let sprites = names.iter().enumerate().map(|(i, name)| sprite(name, 0, (i as u32 * 8, 0, 8, 4), false)).collect();
+    let meta = atlas(Rotation::CounterClockwise, vec![page(0, (64, 16), sprites)]);
+    let dir = WorkDir::new();
+    CssExporter::Css.export(&meta, dir.path()).unwrap();
+    dir.read("atlas.css")
+  }
+
+  fn classes(css: &str) -> Vec<&str> {
+    css.lines().filter_map(|line| line.trim_start().strip_prefix(".sprite-")).map(|rest| rest.trim_end_matches(" {")).collect()
+  }
+
+  #[test]
+  fn colliding_classes_get_suffix() {
+    let css = css(&["a b", "a_b", "a-b", "a.b"]);
+    assert_eq!(classes(&css), ["a_b", "a_b_2", "a-b", "a_b_3"]);
+    assert!(css.contains(".sprite-a_b_2 {\n  background-image: url(\"0.png\");\n  background-position: -8px 0;\n  width: 8px;\n  height: 4px;\n}\n"), "{}", css);
+  }
+
+  #[test]
+  fn retina_sprites_override_their_base() {
+    let css = css(&["hero@2x", "hero", "coin@2x"]);
+    assert_eq!(css, concat!(
+      ".sprite-hero {\n  background-image: url(\"0.png\");\n  background-position: -8px 0;\n  width: 8px;\n  height: 4px;\n}\n",
+      ".sprite-coin {\n  background-image: url(\"0.png\");\n  background-position: -8px 0;\n  background-size: 32px 8px;\n  width: 4px;\n  height: 2px;\n}\n",
+      "@media (-webkit-min-device-pixel-ratio: 2), (min-resolution: 192dpi) {\n",
+      "  .sprite-hero {\n    background-image: url(\"0.png\");\n    background-position: 0 0;\n    background-size: 32px 8px;\n  }\n",
+      "}\n",
+    ));
+  }
+
+  #[test]
+  fn retina_sprites_share_deduped_class() {
+    let css = css(&["a b", "a_b", "a b@2x"]);
+    assert_eq!(classes(&css), ["a_b", "a_b_2", "a_b"]);
+  }
+}
@@ -0,0 +1,143 @@
+extern crate serde_json;
+extern crate serde_yaml;
+extern crate ron;
+
+use super::Exporter;
+use sprack_bin::meta::{AtlasMeta, SpriteMeta, Size, Rotation};
+
+use serde::Serialize;
+use std::fs::File;
+use std::io::{BufWriter, Error, Write, Result};
+use std::path::Path;
+
+// the metadata model as one `<page>.json`, `<page>.yaml` or `<page>.ron` next to every `<page>.png`
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DocumentExporter { Json, Yaml, Ron }
+
+#[derive(Serialize)]
+struct PageDocument<'a> {
+  heuristic: &'a str,
+  rotation: Rotation,
+  pages: usize,
+  index: usize,
+  image: &'a str,
+  size: Size,
+  sprites: &'a [SpriteMeta],
+}
+
+impl Exporter for DocumentExporter {
+  fn name(&self) -> &'static str {
+    match *self {
+      DocumentExporter::Json => "json",
+      DocumentExporter::Yaml => "yaml",
+      DocumentExporter::Ron => "ron",
+    }
+  }
+
+  fn export(&self, meta: &AtlasMeta, dir: &Path) -> Result<()> {
+    for page in &meta.pages {
+      let document = PageDocument {
+        heuristic: &meta.heuristic,
+        rotation: meta.rotation,
+        pages: meta.pages.len(),
+        index: page.index,
+        image: &page.image,
+        size: page.size,
+        sprites: &page.sprites,
+      };
+      let path = dir.join(&page.image).with_extension(self.name());
+      match *self {
+        DocumentExporter::Json => write_json(&path, &document)?,
+        DocumentExporter::Yaml => {
+          let mut out = BufWriter::new(File::create(path)?);
+          serde_yaml::to_writer(&mut out, &document).map_err(Error::other)?;
+          out.flush()?;
+        }
+        DocumentExporter::Ron => {
+          let ron = ron::ser::to_string_pretty(&document, ron::ser::PrettyConfig::default()).map_err(Error::other)?;
+          File::create(path)?.write_all(ron.as_bytes())?;
+        }
+      }
+    }
+    Ok(())
+  }
+}
+
+pub fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<()> {
+  let mut out = BufWriter::new(File::create(path)?);
+  serde_json::to_writer_pretty(&mut out, value)?;
+  out.flush()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use super::serde_json::{Value, json};
+  use sprack_bin::testing::{WorkDir, atlas, page, sprite};
+
+  fn export(exporter: DocumentExporter) -> WorkDir {
+    let meta = atlas(Rotation::CounterClockwise, vec![
+      page(0, (64, 32), vec![sprite("hero", 0, (0, 0, 16, 24), false), sprite("tree", 0, (16, 0, 20, 8), true)]),
+      page(1, (8, 8), vec![sprite("coin", 1, (0, 0, 8, 8), false)]),
+    ]);
+    let dir = WorkDir::new();
+    exporter.export(&meta, dir.path()).unwrap();
+    dir
+  }
+
+  fn first_page() -> Value {
+    json!({
+      "heuristic": "area",
+      "rotation": "counter_clockwise",
+      "pages": 2,
+      "index": 0,
+      "image": "0.png",
+      "size": { "w": 64, "h": 32 },
+      "sprites": [
+        { "name": "hero", "page": 0, "x": 0, "y": 0, "w": 16, "h": 24, "rotated": false, "original": { "w": 16, "h": 24 } },
+        { "name": "tree", "page": 0, "x": 16, "y": 0, "w": 20, "h": 8, "rotated": true, "original": { "w": 8, "h": 20 } },
+      ],
+    })
+  }
+
+  #[test]
+  fn json_is_written_per_page() {
+    let dir = export(DocumentExporter::Json);
+    let first: Value = serde_json::from_str(&dir.read("0.json")).unwrap();
+    assert_eq!(first, first_page());
+
+    let second: Value = serde_json::from_str(&dir.read("1.json")).unwrap();
+    assert_eq!(second["index"], 1);
+    assert_eq!(second["image"], "1.png");
+    assert_eq!(second["sprites"][0]["name"], "coin");
+  }
+
+  // small documents fit the buffer, so the write only fails when it is flushed
+  #[test]
+  #[cfg(target_os = "linux")]
+  fn json_write_errors_are_reported() {
+    assert!(write_json(Path::new("/dev/full"), &first_page()).is_err());
+  }
+
+  #[test]
+  fn yaml_has_the_same_content() {
+    let dir = export(DocumentExporter::Yaml);
+    let first: Value = serde_yaml::from_str(&dir.read("0.yaml")).unwrap();
+    assert_eq!(first, first_page());
+    let second: Value = serde_yaml::from_str(&dir.read("1.yaml")).unwrap();
+    assert_eq!(second["sprites"][0]["name"], "coin");
+  }
+
+  #[test]
+  fn ron_has_the_same_content() {
+    let dir = export(DocumentExporter::Ron);
+    let text = dir.read("0.ron");
+    // enum variants are bare identifiers in RON and read back as unit
+    assert!(text.contains("rotation: counter_clockwise,"), "{}", text);
+    let mut first: Value = ron::from_str(&text).unwrap();
+    first["rotation"] = json!("counter_clockwise");
+    assert_eq!(first, first_page());
+    let second: Value = ron::from_str(&dir.read("1.ron")).unwrap();
+    assert_eq!(second["sprites"][0]["name"], "coin");
+  }
+}
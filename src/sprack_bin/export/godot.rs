@@ -0,0 +1,63 @@
+use super::Exporter;
+use sprack_bin::meta::AtlasMeta;
+
+use std::fs::File;
+use std::io::{BufWriter, Write, Result};
+use std::path::Path;
+
+// Godot 4 `AtlasTexture` resource per sprite, `<name>.tres` referring to its page image
+// by a path relative to the resource
+pub struct GodotExporter;
+
+impl Exporter for GodotExporter {
+  fn name(&self) -> &'static str { "godot" }
+
+  fn supports_rotation(&self) -> bool { false }
+
+  fn export(&self, meta: &AtlasMeta, dir: &Path) -> Result<()> {
+    for page in &meta.pages {
+      for s in &page.sprites {
+        let mut out = BufWriter::new(File::create(dir.join(format!("{}.tres", s.name)))?);
+        writeln!(out, r#"[gd_resource type="AtlasTexture" load_steps=2 format=3]"#)?;
+        writeln!(out)?;
+        writeln!(out, r#"[ext_resource type="Texture2D" path="{}" id="1"]"#, page.image)?;
+        writeln!(out)?;
+        writeln!(out, "[resource]")?;
+        writeln!(out, r#"atlas = ExtResource("1")"#)?;
+        writeln!(out, "region = Rect2({}, {}, {}, {})", s.x, s.y, s.w, s.h)?;
+        out.flush()?;
+      }
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use super::super::draw_rotation;
+  use sprack_bin::meta::Rotation;
+  use sprack_bin::testing::{WorkDir, atlas, page, sprite};
+
+  #[test]
+  fn regions_are_written_per_sprite() {
+    let meta = atlas(Rotation::CounterClockwise, vec![
+      page(0, (48, 80), vec![sprite("hero", 0, (10, 20, 12, 30), false)]),
+      page(1, (16, 8), vec![sprite("coin", 1, (4, 0, 8, 8), false)]),
+    ]);
+    let dir = WorkDir::new();
+    GodotExporter.export(&meta, dir.path()).unwrap();
+
+    let hero = dir.read("hero.tres");
+    assert!(hero.contains(r#"[ext_resource type="Texture2D" path="0.png" id="1"]"#), "{}", hero);
+    assert!(hero.contains("region = Rect2(10, 20, 12, 30)\n"), "{}", hero);
+    let coin = dir.read("coin.tres");
+    assert!(coin.contains(r#"path="1.png""#), "{}", coin);
+    assert!(coin.contains("region = Rect2(4, 0, 8, 8)\n"), "{}", coin);
+  }
+
+  #[test]
+  fn rotated_sprites_are_refused() {
+    assert!(draw_rotation(&[&GodotExporter], true).is_err());
+  }
+}
@@ -0,0 +1,97 @@
+use super::Exporter;
+use sprack_bin::meta::{AtlasMeta, Rotation};
+
+use std::fs::File;
+use std::io::{BufWriter, Write, Result};
+use std::path::Path;
+
+// libGDX (and Spine) `TextureAtlas` text format, all pages in one `atlas.atlas`.
+// `size` keeps the unrotated size and rotated regions are turned counter-clockwise
+pub struct LibGdxExporter;
+
+impl Exporter for LibGdxExporter {
+  fn name(&self) -> &'static str { "libgdx" }
+
+  fn rotation(&self) -> Option<Rotation> { Some(Rotation::CounterClockwise) }
+
+  fn export(&self, meta: &AtlasMeta, dir: &Path) -> Result<()> {
+    let mut out = BufWriter::new(File::create(dir.join("atlas.atlas"))?);
+    for page in &meta.pages {
+      writeln!(out)?;
+      writeln!(out, "{}", page.image)?;
+      writeln!(out, "size: {}, {}", page.size.w, page.size.h)?;
+      writeln!(out, "format: RGBA8888")?;
+      writeln!(out, "filter: Nearest, Nearest")?;
+      writeln!(out, "repeat: none")?;
+      for sprite in &page.sprites {
+        writeln!(out, "{}", sprite.name)?;
+        writeln!(out, "  rotate: {}", sprite.rotated)?;
+        writeln!(out, "  xy: {}, {}", sprite.x, sprite.y)?;
+        writeln!(out, "  size: {}, {}", sprite.original.w, sprite.original.h)?;
+        writeln!(out, "  orig: {}, {}", sprite.original.w, sprite.original.h)?;
+        writeln!(out, "  offset: 0, 0")?;
+        writeln!(out, "  index: -1")?;
+      }
+    }
+    out.flush()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use super::super::draw_rotation;
+  use sprack_bin::testing::{WorkDir, atlas, page, sprite};
+
+  #[test]
+  fn pages_are_listed_in_one_file() {
+    // `tree` takes 20x8 in the atlas, it is 8x20 unrotated
+    let meta = atlas(Rotation::CounterClockwise, vec![
+      page(0, (64, 32), vec![sprite("hero", 0, (0, 0, 16, 24), false), sprite("tree", 0, (16, 0, 20, 8), true)]),
+      page(1, (8, 8), vec![sprite("coin", 1, (0, 0, 8, 8), false)]),
+    ]);
+    let dir = WorkDir::new();
+    LibGdxExporter.export(&meta, dir.path()).unwrap();
+
+    assert_eq!(dir.read("atlas.atlas"), concat!(
+      "\n",
+      "0.png\n",
+      "size: 64, 32\n",
+      "format: RGBA8888\n",
+      "filter: Nearest, Nearest\n",
+      "repeat: none\n",
+      "hero\n",
+      "  rotate: false\n",
+      "  xy: 0, 0\n",
+      "  size: 16, 24\n",
+      "  orig: 16, 24\n",
+      "  offset: 0, 0\n",
+      "  index: -1\n",
+      "tree\n",
+      "  rotate: true\n",
+      "  xy: 16, 0\n",
+      "  size: 8, 20\n",
+      "  orig: 8, 20\n",
+      "  offset: 0, 0\n",
+      "  index: -1\n",
+      "\n",
+      "1.png\n",
+      "size: 8, 8\n",
+      "format: RGBA8888\n",
+      "filter: Nearest, Nearest\n",
+      "repeat: none\n",
+      "coin\n",
+      "  rotate: false\n",
+      "  xy: 0, 0\n",
+      "  size: 8, 8\n",
+      "  orig: 8, 8\n",
+      "  offset: 0, 0\n",
+      "  index: -1\n",
+    ));
+  }
+
+  #[test]
+  fn sprites_are_drawn_counter_clockwise() {
+    assert_eq!(draw_rotation(&[&LibGdxExporter], true), Ok(Rotation::CounterClockwise));
+  }
+}
@@ -0,0 +1,92 @@
+mod document;
+mod texturepacker;
+mod libgdx;
+mod cocos2d;
+mod starling;
+mod godot;
+mod unity;
+mod bevy;
+mod css;
+mod template;
+mod code;
+mod protobuf;
+mod tiled;
+
+pub use self::document::DocumentExporter;
+pub use self::texturepacker::TexturePackerExporter;
+pub use self::libgdx::LibGdxExporter;
+pub use self::cocos2d::Cocos2dExporter;
+pub use self::starling::StarlingExporter;
+pub use self::godot::GodotExporter;
+pub use self::unity::UnityExporter;
+pub use self::bevy::BevyExporter;
+pub use self::css::CssExporter;
+pub use self::template::TemplateExporter;
+pub use self::code::CodeExporter;
+pub use self::protobuf::ProtobufExporter;
+pub use self::tiled::TiledExporter;
+
+use sprack_bin::meta::{AtlasMeta, Rotation};
+
+use std::io;
+use std::path::Path;
+
+const EXPORTERS: &[&dyn Exporter] = &[
+  &DocumentExporter::Json,
+  &DocumentExporter::Yaml,
+  &DocumentExporter::Ron,
+  &ProtobufExporter,
+  &TexturePackerExporter::Hash,
+  &TexturePackerExporter::Array,
+  &LibGdxExporter,
+  &Cocos2dExporter,
+  &StarlingExporter,
+  &GodotExporter,
+  &UnityExporter,
+  &BevyExporter::Ron,
+  &BevyExporter::Json,
+  &CssExporter::Css,
+  &CssExporter::Scss,
+  &CodeExporter::Rust,
+  &CodeExporter::C,
+  &TiledExporter,
+];
+
+// writes metadata of a packing result into the dir holding its atlas images
+pub trait Exporter: Sync {
+  fn name(&self) -> &'static str;
+  // direction the format expects rotated sprites in, `None` if the metadata tells it
+  fn rotation(&self) -> Option<Rotation> { None }
+  // formats with plain rectangles can not describe rotated sprites at all
+  fn supports_rotation(&self) -> bool { true }
+  fn export(&self, meta: &AtlasMeta, dir: &Path) -> io::Result<()>;
+}
+
+pub fn exporter_by_name(name: &str) -> Option<&'static dyn Exporter> {
+  EXPORTERS.iter().find(|e| e.name() == name).cloned()
+}
+
+pub fn exporter_names() -> Vec<&'static str> {
+  EXPORTERS.iter().map(|e| e.name()).collect()
+}
+
+// sprites are drawn once for all formats, so with flipping they have to agree on the direction
+pub fn draw_rotation(exporters: &[&dyn Exporter], flipping: bool) -> Result<Rotation, String> {
+  if !flipping { return Ok(Rotation::CounterClockwise); }
+  if let Some(e) = exporters.iter().find(|e| !e.supports_rotation()) {
+    return Err(format!("{} format does not support rotated sprites, --flipping can not be used with it", e.name()));
+  }
+  let mut required = exporters.iter().filter_map(|e| e.rotation().map(|r| (e.name(), r)));
+  match required.next() {
+    None => Ok(Rotation::CounterClockwise),
+    Some((name, rotation)) => match required.find(|&(_, r)| r != rotation) {
+      None => Ok(rotation),
+      Some((other, _)) => Err(format!("{} and {} formats rotate sprites in different directions", name, other)),
+    },
+  }
+}
+
+// for names and paths put into XML text and attributes
+fn escape_xml(text: &str) -> String {
+  text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;").replace('\'', "&apos;")
+}
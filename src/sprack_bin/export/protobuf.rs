@@ -0,0 +1,143 @@
+use super::Exporter;
+use sprack_bin::meta::{AtlasMeta, PageMeta, SpriteMeta, Size, Rotation};
+
+use std::fs::File;
+use std::io::{Write, Result};
+use std::path::Path;
+
+const VARINT: u32 = 0;
+const LENGTH_DELIMITED: u32 = 2;
+
+// all pages in one `atlas.pb`, an `Atlas` message of `schema/atlas.proto`.
+// Field numbers here have to be kept in sync with the schema
+pub struct ProtobufExporter;
+
+impl Exporter for ProtobufExporter {
+  fn name(&self) -> &'static str { "protobuf" }
+
+  fn export(&self, meta: &AtlasMeta, dir: &Path) -> Result<()> {
+    File::create(dir.join("atlas.pb"))?.write_all(&atlas(meta).0)
+  }
+}
+
+fn atlas(meta: &AtlasMeta) -> Message {
+  let mut atlas = Message::default();
+  atlas.string(1, &meta.heuristic);
+  atlas.uint(2, match meta.rotation { Rotation::CounterClockwise => 0, Rotation::Clockwise => 1 });
+  for page in &meta.pages { atlas.message(3, &self::page(page)); }
+  atlas
+}
+
+fn page(page: &PageMeta) -> Message {
+  let mut msg = Message::default();
+  msg.uint(1, page.index as u64);
+  msg.string(2, &page.image);
+  msg.message(3, &size(page.size));
+  for s in &page.sprites { msg.message(4, &sprite(s)); }
+  msg
+}
+
+fn sprite(sprite: &SpriteMeta) -> Message {
+  let mut msg = Message::default();
+  msg.string(1, &sprite.name);
+  msg.uint(2, sprite.page as u64);
+  msg.uint(3, sprite.x.into());
+  msg.uint(4, sprite.y.into());
+  msg.uint(5, sprite.w.into());
+  msg.uint(6, sprite.h.into());
+  msg.uint(7, sprite.rotated.into());
+  msg.message(8, &size(sprite.original));
+  msg
+}
+
+fn size(size: Size) -> Message {
+  let mut msg = Message::default();
+  msg.uint(1, size.w.into());
+  msg.uint(2, size.h.into());
+  msg
+}
+
+// proto3 wire format, fields with default values are left out
+#[derive(Default)]
+struct Message(Vec<u8>);
+
+impl Message {
+  fn uint(&mut self, field: u32, value: u64) {
+    if value == 0 { return; }
+    self.key(field, VARINT);
+    self.varint(value);
+  }
+
+  fn string(&mut self, field: u32, value: &str) {
+    if value.is_empty() { return; }
+    self.bytes(field, value.as_bytes());
+  }
+
+  // nested and repeated messages are written even when empty
+  fn message(&mut self, field: u32, value: &Message) {
+    self.bytes(field, &value.0);
+  }
+
+  fn bytes(&mut self, field: u32, value: &[u8]) {
+    self.key(field, LENGTH_DELIMITED);
+    self.varint(value.len() as u64);
+    self.0.extend_from_slice(value);
+  }
+
+  fn key(&mut self, field: u32, wire_type: u32) {
+    self.varint(u64::from(field << 3 | wire_type));
+  }
+
+  fn varint(&mut self, mut value: u64) {
+    while value >= 0x80 {
+      self.0.push(value as u8 | 0x80);
+      value >>= 7;
+    }
+    self.0.push(value as u8);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use sprack_bin::testing::{WorkDir, atlas, page, sprite};
+
+  #[test]
+  fn atlas_matches_schema() {
+    let meta = atlas(Rotation::Clockwise, vec![page(0, (300, 2), vec![sprite("a", 0, (0, 1, 2, 1), true)])]);
+    let dir = WorkDir::new();
+    ProtobufExporter.export(&meta, dir.path()).unwrap();
+
+    let sprite = [
+      0x0a, 0x01, b'a', // name
+      0x20, 0x01, // y, page and x are 0 and left out
+      0x28, 0x02, // w
+      0x30, 0x01, // h
+      0x38, 0x01, // rotated
+      0x42, 0x04, 0x08, 0x01, 0x10, 0x02, // original
+    ];
+    let mut page = vec![
+      0x12, 0x05, b'0', b'.', b'p', b'n', b'g', // image, index 0 is left out
+      0x1a, 0x05, 0x08, 0xac, 0x02, 0x10, 0x02, // size, 300 takes two bytes
+      0x22, sprite.len() as u8,
+    ];
+    page.extend_from_slice(&sprite);
+    let mut expected = vec![
+      0x0a, 0x04, b'a', b'r', b'e', b'a', // heuristic
+      0x10, 0x01, // clockwise rotation
+      0x1a, page.len() as u8,
+    ];
+    expected.extend_from_slice(&page);
+
+    assert_eq!(dir.read_bytes("atlas.pb"), expected);
+  }
+
+  #[test]
+  fn defaults_are_left_out() {
+    let mut meta = atlas(Rotation::CounterClockwise, vec![page(0, (0, 0), vec![])]);
+    meta.heuristic.clear();
+    meta.pages[0].image.clear();
+    // only the page and its empty size remain
+    assert_eq!(super::atlas(&meta).0, [0x1a, 0x02, 0x1a, 0x00]);
+  }
+}
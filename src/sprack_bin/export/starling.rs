@@ -0,0 +1,70 @@
+use super::{Exporter, escape_xml};
+use sprack_bin::meta::{AtlasMeta, Rotation};
+
+use std::fs::File;
+use std::io::{BufWriter, Write, Result};
+use std::path::Path;
+
+// Sparrow/Starling `TextureAtlas` XML, one `<page>.xml` per page.
+// `SubTexture` sizes are the area in the atlas and rotated ones are turned clockwise
+pub struct StarlingExporter;
+
+impl Exporter for StarlingExporter {
+  fn name(&self) -> &'static str { "starling" }
+
+  fn rotation(&self) -> Option<Rotation> { Some(Rotation::Clockwise) }
+
+  fn export(&self, meta: &AtlasMeta, dir: &Path) -> Result<()> {
+    for page in &meta.pages {
+      let mut out = BufWriter::new(File::create(dir.join(&page.image).with_extension("xml"))?);
+      writeln!(out, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+      writeln!(out, r#"<TextureAtlas imagePath="{}" width="{}" height="{}">"#, escape_xml(&page.image), page.size.w, page.size.h)?;
+      for s in &page.sprites {
+        write!(out, r#"  <SubTexture name="{}" x="{}" y="{}" width="{}" height="{}""#, escape_xml(&s.name), s.x, s.y, s.w, s.h)?;
+        if s.rotated { write!(out, r#" rotated="true""#)?; }
+        writeln!(out, "/>")?;
+      }
+      writeln!(out, "</TextureAtlas>")?;
+      out.flush()?;
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use super::super::{LibGdxExporter, draw_rotation};
+  use sprack_bin::testing::{WorkDir, atlas, page, sprite};
+
+  #[test]
+  fn rotated_sub_textures_take_atlas_area() {
+    // `tree` takes 20x8 in the atlas, it is 8x20 unrotated
+    let meta = atlas(Rotation::Clockwise, vec![
+      page(0, (64, 32), vec![sprite("hero", 0, (0, 0, 16, 24), false), sprite("tree", 0, (16, 0, 20, 8), true)]),
+      page(1, (8, 8), vec![sprite("a\"b<c>", 1, (0, 0, 8, 8), false)]),
+    ]);
+    let dir = WorkDir::new();
+    StarlingExporter.export(&meta, dir.path()).unwrap();
+
+    assert_eq!(dir.read("0.xml"), concat!(
+      "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+      "<TextureAtlas imagePath=\"0.png\" width=\"64\" height=\"32\">\n",
+      "  <SubTexture name=\"hero\" x=\"0\" y=\"0\" width=\"16\" height=\"24\"/>\n",
+      "  <SubTexture name=\"tree\" x=\"16\" y=\"0\" width=\"20\" height=\"8\" rotated=\"true\"/>\n",
+      "</TextureAtlas>\n",
+    ));
+    assert_eq!(dir.read("1.xml"), concat!(
+      "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+      "<TextureAtlas imagePath=\"1.png\" width=\"8\" height=\"8\">\n",
+      "  <SubTexture name=\"a&quot;b&lt;c&gt;\" x=\"0\" y=\"0\" width=\"8\" height=\"8\"/>\n",
+      "</TextureAtlas>\n",
+    ));
+  }
+
+  #[test]
+  fn sprites_are_drawn_clockwise() {
+    assert_eq!(draw_rotation(&[&StarlingExporter], true), Ok(Rotation::Clockwise));
+    assert!(draw_rotation(&[&StarlingExporter, &LibGdxExporter], true).is_err());
+  }
+}
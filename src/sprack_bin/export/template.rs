@@ -0,0 +1,159 @@
+extern crate handlebars;
+
+use self::handlebars::{Handlebars, no_escape};
+use super::Exporter;
+use sprack_bin::meta::{AtlasMeta, PageMeta, SpriteMeta, Size, Rotation};
+
+use std::fs::{OpenOptions, read_to_string};
+use std::io::{BufWriter, Error, ErrorKind, Write, Result};
+use std::path::Path;
+
+const TEMPLATE_NAME: &str = "metadata";
+
+// renders a user supplied Handlebars template once per pack. The output is named after the template
+// without its `.hbs` / `.handlebars` extension, so `atlas.txt.hbs` produces `atlas.txt`. It runs
+// after atlas images and all other formats are written and never overwrites any of them
+pub struct TemplateExporter {
+  output: String,
+  registry: Handlebars<'static>,
+}
+
+// what templates see: `heuristic`, `rotation` (`clockwise` / `counter_clockwise`), `pages` with
+// their `sprites` and all `sprites` at once. Sprites are packed untrimmed, so `offset` of the packed
+// area inside the original image is always zero for now
+#[derive(Serialize)]
+struct Context<'a> {
+  heuristic: &'a str,
+  rotation: Rotation,
+  pages: Vec<PageContext<'a>>,
+  sprites: Vec<SpriteContext<'a>>,
+}
+
+#[derive(Serialize)]
+struct PageContext<'a> {
+  index: usize,
+  image: &'a str,
+  size: Size,
+  sprites: Vec<SpriteContext<'a>>,
+}
+
+#[derive(Serialize)]
+struct SpriteContext<'a> {
+  #[serde(flatten)]
+  sprite: &'a SpriteMeta,
+  image: &'a str,
+  offset: Offset,
+}
+
+#[derive(Serialize)]
+struct Offset { x: u32, y: u32 }
+
+impl TemplateExporter {
+  pub fn new(path: &Path) -> Result<TemplateExporter> {
+    let source = read_to_string(path)?;
+    let mut registry = Handlebars::new();
+    // metadata is rarely HTML
+    registry.register_escape_fn(no_escape);
+    registry.register_template_string(TEMPLATE_NAME, source)
+      .map_err(|e| Error::new(ErrorKind::InvalidData, e.to_string()))?;
+
+    let file_name = path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+    let output = [".hbs", ".handlebars"].iter()
+      .find_map(|ext| file_name.strip_suffix(ext))
+      .unwrap_or(&file_name)
+      .to_string();
+    if output.is_empty() || output.starts_with('.') {
+      return Err(Error::new(ErrorKind::InvalidInput, format!("can not name the output after template {:?}", file_name)));
+    }
+    Ok(TemplateExporter { output, registry })
+  }
+}
+
+impl Exporter for TemplateExporter {
+  fn name(&self) -> &'static str { "template" }
+
+  fn export(&self, meta: &AtlasMeta, dir: &Path) -> Result<()> {
+    let pages = meta.pages.iter().map(|page| PageContext {
+      index: page.index,
+      image: &page.image,
+      size: page.size,
+      sprites: page.sprites.iter().map(|sprite| sprite_context(page, sprite)).collect(),
+    }).collect::<Vec<_>>();
+    let sprites = meta.pages.iter()
+      .flat_map(|page| page.sprites.iter().map(move |sprite| sprite_context(page, sprite)))
+      .collect();
+    let context = Context { heuristic: &meta.heuristic, rotation: meta.rotation, pages, sprites };
+
+    let file = OpenOptions::new().write(true).create_new(true).open(dir.join(&self.output)).map_err(|e| match e.kind() {
+      ErrorKind::AlreadyExists => Error::new(e.kind(), format!("template output {:?} would overwrite an atlas image or other metadata", self.output)),
+      _ => e,
+    })?;
+    let mut out = BufWriter::new(file);
+    self.registry.render_to_write(TEMPLATE_NAME, &context, &mut out)
+      .map_err(|e| Error::other(e.to_string()))?;
+    out.flush()
+  }
+}
+
+fn sprite_context<'a>(page: &'a PageMeta, sprite: &'a SpriteMeta) -> SpriteContext<'a> {
+  SpriteContext { sprite, image: &page.image, offset: Offset { x: 0, y: 0 } }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::fs::write;
+  use sprack_bin::testing::{WorkDir, atlas, page, sprite};
+
+  fn template(dir: &WorkDir, name: &str, source: &str) -> Result<TemplateExporter> {
+    let path = dir.path().join("templates");
+    ::std::fs::create_dir_all(&path)?;
+    write(path.join(name), source)?;
+    TemplateExporter::new(&path.join(name))
+  }
+
+  #[test]
+  fn renders_pages_and_sprites() {
+    let dir = WorkDir::new();
+    let source = concat!(
+      "{{heuristic}} {{rotation}}\n",
+      "{{#each pages}}page {{index}} {{image}} {{size.w}}x{{size.h}}:{{#each sprites}} {{name}}{{/each}}\n{{/each}}",
+      "{{#each sprites}}{{name}} {{image}} {{x}},{{y}} {{w}}x{{h}} {{rotated}} {{original.w}}x{{original.h}} +{{offset.x}},{{offset.y}}\n{{/each}}",
+    );
+    let exporter = template(&dir, "atlas.txt.hbs", source).unwrap();
+    let meta = atlas(Rotation::Clockwise, vec![
+      page(0, (64, 32), vec![sprite("hero", 0, (0, 0, 16, 24), false), sprite("a<b>", 0, (16, 0, 20, 8), true)]),
+      page(1, (8, 8), vec![sprite("coin", 1, (0, 0, 8, 8), false)]),
+    ]);
+    exporter.export(&meta, dir.path()).unwrap();
+
+    assert_eq!(dir.read("atlas.txt"), concat!(
+      "area clockwise\n",
+      "page 0 0.png 64x32: hero a<b>\n",
+      "page 1 1.png 8x8: coin\n",
+      "hero 0.png 0,0 16x24 false 16x24 +0,0\n",
+      "a<b> 0.png 16,0 20x8 true 8x20 +0,0\n",
+      "coin 1.png 0,0 8x8 false 8x8 +0,0\n",
+    ));
+  }
+
+  #[test]
+  fn output_never_overwrites_other_files() {
+    let dir = WorkDir::new();
+    write(dir.path().join("0.png"), "atlas").unwrap();
+    let exporter = template(&dir, "0.png.hbs", "{{heuristic}}").unwrap();
+    let meta = atlas(Rotation::Clockwise, vec![page(0, (8, 8), vec![])]);
+
+    let err = exporter.export(&meta, dir.path()).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::AlreadyExists);
+    assert_eq!(dir.read("0.png"), "atlas");
+  }
+
+  #[test]
+  fn output_needs_a_name() {
+    let dir = WorkDir::new();
+    for name in &[".hbs", ".handlebars", ".hidden"] {
+      assert_eq!(template(&dir, name, "{{heuristic}}").err().map(|e| e.kind()), Some(ErrorKind::InvalidInput), "{}", name);
+    }
+  }
+}
@@ -0,0 +1,150 @@
+use super::Exporter;
+use super::document::write_json;
+use sprack_bin::meta::{AtlasMeta, SpriteMeta, Size, Rotation};
+
+use serde::{Serialize, Serializer};
+use std::io::Result;
+use std::path::Path;
+
+// TexturePacker's JSON as read by Phaser, PixiJS and the like, one file per page.
+// `frame` keeps the unrotated size and rotated sprites are turned clockwise
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TexturePackerExporter { Hash, Array }
+
+#[derive(Serialize)]
+struct Sheet<'a> {
+  frames: Frames<'a>,
+  meta: Meta<'a>,
+}
+
+struct Frames<'a> {
+  sprites: &'a [SpriteMeta],
+  hash: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Frame<'a> {
+  #[serde(skip_serializing_if = "Option::is_none")]
+  filename: Option<&'a str>,
+  frame: Rect,
+  rotated: bool,
+  trimmed: bool,
+  sprite_source_size: Rect,
+  source_size: Size,
+}
+
+#[derive(Serialize)]
+struct Rect { x: u32, y: u32, w: u32, h: u32 }
+
+#[derive(Serialize)]
+struct Meta<'a> {
+  app: &'static str,
+  version: &'static str,
+  image: &'a str,
+  format: &'static str,
+  size: Size,
+  scale: &'static str,
+}
+
+impl Exporter for TexturePackerExporter {
+  fn name(&self) -> &'static str {
+    match *self {
+      TexturePackerExporter::Hash => "texturepacker_hash",
+      TexturePackerExporter::Array => "texturepacker_array",
+    }
+  }
+
+  fn rotation(&self) -> Option<Rotation> { Some(Rotation::Clockwise) }
+
+  fn export(&self, meta: &AtlasMeta, dir: &Path) -> Result<()> {
+    let hash = *self == TexturePackerExporter::Hash;
+    for page in &meta.pages {
+      let sheet = Sheet {
+        frames: Frames { sprites: &page.sprites, hash },
+        meta: Meta { app: "sprack", version: "1.0", image: &page.image, format: "RGBA8888", size: page.size, scale: "1" },
+      };
+      // `.json` is taken by the plain exporter
+      let ext = if hash { "hash.json" } else { "array.json" };
+      write_json(&dir.join(&page.image).with_extension(ext), &sheet)?;
+    }
+    Ok(())
+  }
+}
+
+// hash keys frames by name, array lists them with a `filename`, both in placement order
+impl<'a> Serialize for Frames<'a> {
+  fn serialize<S: Serializer>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error> {
+    if self.hash {
+      serializer.collect_map(self.sprites.iter().map(|s| (&s.name, frame(s, false))))
+    } else {
+      serializer.collect_seq(self.sprites.iter().map(|s| frame(s, true)))
+    }
+  }
+}
+
+fn frame(sprite: &SpriteMeta, named: bool) -> Frame<'_> {
+  let Size { w, h } = sprite.original;
+  Frame {
+    filename: if named { Some(&sprite.name) } else { None },
+    frame: Rect { x: sprite.x, y: sprite.y, w, h },
+    rotated: sprite.rotated,
+    trimmed: false,
+    sprite_source_size: Rect { x: 0, y: 0, w, h },
+    source_size: sprite.original,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  extern crate serde_json;
+
+  use super::*;
+  use super::super::draw_rotation;
+  use self::serde_json::{Value, from_str, json};
+  use sprack_bin::testing::{WorkDir, atlas, page, sprite};
+
+  fn export(exporter: TexturePackerExporter, file: &str) -> Value {
+    let meta = atlas(Rotation::Clockwise, vec![
+      page(0, (64, 32), vec![sprite("hero", 0, (0, 0, 16, 24), false), sprite("tree", 0, (16, 0, 20, 8), true)]),
+    ]);
+    let dir = WorkDir::new();
+    exporter.export(&meta, dir.path()).unwrap();
+    from_str(&dir.read(file)).unwrap()
+  }
+
+  #[test]
+  fn hash_keys_frames_by_name() {
+    let sheet = export(TexturePackerExporter::Hash, "0.hash.json");
+    let tree = &sheet["frames"]["tree"];
+    assert_eq!(tree["rotated"], true);
+    // TexturePacker keeps the unrotated size in `frame`
+    assert_eq!(tree["frame"], json!({ "x": 16, "y": 0, "w": 8, "h": 20 }));
+    assert_eq!(tree["spriteSourceSize"], json!({ "x": 0, "y": 0, "w": 8, "h": 20 }));
+    assert_eq!(tree["sourceSize"], json!({ "w": 8, "h": 20 }));
+    assert!(tree.get("filename").is_none());
+
+    assert_eq!(sheet["frames"]["hero"]["rotated"], false);
+    assert_eq!(sheet["frames"]["hero"]["frame"], json!({ "x": 0, "y": 0, "w": 16, "h": 24 }));
+    assert_eq!(sheet["meta"]["image"], "0.png");
+    assert_eq!(sheet["meta"]["size"], json!({ "w": 64, "h": 32 }));
+  }
+
+  #[test]
+  fn array_lists_frames_in_order() {
+    let sheet = export(TexturePackerExporter::Array, "0.array.json");
+    let frames = sheet["frames"].as_array().unwrap();
+    assert_eq!(frames.len(), 2);
+    assert_eq!(frames[0]["filename"], "hero");
+    assert_eq!(frames[1]["filename"], "tree");
+    assert_eq!(frames[1]["rotated"], true);
+    assert_eq!(frames[1]["frame"], json!({ "x": 16, "y": 0, "w": 8, "h": 20 }));
+  }
+
+  #[test]
+  fn sprites_are_drawn_clockwise() {
+    for exporter in [TexturePackerExporter::Hash, TexturePackerExporter::Array] {
+      assert_eq!(draw_rotation(&[&exporter], true), Ok(Rotation::Clockwise));
+    }
+  }
+}
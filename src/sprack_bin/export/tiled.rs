@@ -0,0 +1,80 @@
+use super::{Exporter, escape_xml};
+use sprack_bin::meta::AtlasMeta;
+
+use std::cmp::max;
+use std::fs::File;
+use std::io::{BufWriter, Write, Result};
+use std::path::Path;
+
+// Tiled collection-of-images tileset in `atlas.tsx`, every tile is a sub-rectangle of its page
+// image (Tiled 1.9+) with the sprite name in a `name` property. Tile ids follow placement order
+pub struct TiledExporter;
+
+impl Exporter for TiledExporter {
+  fn name(&self) -> &'static str { "tiled" }
+
+  fn supports_rotation(&self) -> bool { false }
+
+  fn export(&self, meta: &AtlasMeta, dir: &Path) -> Result<()> {
+    let sprites = meta.pages.iter().flat_map(|page| page.sprites.iter().map(move |s| (page, s))).collect::<Vec<_>>();
+    let (tile_w, tile_h) = sprites.iter().fold((1, 1), |acc, &(_, s)| (max(acc.0, s.w), max(acc.1, s.h)));
+
+    let mut out = BufWriter::new(File::create(dir.join("atlas.tsx"))?);
+    writeln!(out, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(out, r#"<tileset version="1.10" tiledversion="1.10.2" name="atlas" tilewidth="{}" tileheight="{}" tilecount="{}" columns="0">"#,
+      tile_w, tile_h, sprites.len())?;
+    writeln!(out, r#" <grid orientation="orthogonal" width="1" height="1"/>"#)?;
+    for (id, (page, s)) in sprites.iter().enumerate() {
+      writeln!(out, r#" <tile id="{}" x="{}" y="{}" width="{}" height="{}">"#, id, s.x, s.y, s.w, s.h)?;
+      writeln!(out, "  <properties>")?;
+      writeln!(out, r#"   <property name="name" value="{}"/>"#, escape_xml(&s.name))?;
+      writeln!(out, "  </properties>")?;
+      writeln!(out, r#"  <image source="{}" width="{}" height="{}"/>"#, escape_xml(&page.image), page.size.w, page.size.h)?;
+      writeln!(out, " </tile>")?;
+    }
+    writeln!(out, "</tileset>")?;
+    out.flush()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use sprack_bin::meta::Rotation;
+  use sprack_bin::testing::{WorkDir, atlas, page, sprite};
+
+  #[test]
+  fn tiles_are_sub_rects_of_pages() {
+    let meta = atlas(Rotation::CounterClockwise, vec![
+      page(0, (64, 32), vec![sprite("hero", 0, (0, 0, 16, 24), false), sprite("tree", 0, (16, 0, 20, 8), false)]),
+      page(1, (8, 8), vec![sprite("a&b", 1, (1, 2, 6, 5), false)]),
+    ]);
+    let dir = WorkDir::new();
+    TiledExporter.export(&meta, dir.path()).unwrap();
+
+    assert_eq!(dir.read("atlas.tsx"), concat!(
+      "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+      "<tileset version=\"1.10\" tiledversion=\"1.10.2\" name=\"atlas\" tilewidth=\"20\" tileheight=\"24\" tilecount=\"3\" columns=\"0\">\n",
+      " <grid orientation=\"orthogonal\" width=\"1\" height=\"1\"/>\n",
+      " <tile id=\"0\" x=\"0\" y=\"0\" width=\"16\" height=\"24\">\n",
+      "  <properties>\n",
+      "   <property name=\"name\" value=\"hero\"/>\n",
+      "  </properties>\n",
+      "  <image source=\"0.png\" width=\"64\" height=\"32\"/>\n",
+      " </tile>\n",
+      " <tile id=\"1\" x=\"16\" y=\"0\" width=\"20\" height=\"8\">\n",
+      "  <properties>\n",
+      "   <property name=\"name\" value=\"tree\"/>\n",
+      "  </properties>\n",
+      "  <image source=\"0.png\" width=\"64\" height=\"32\"/>\n",
+      " </tile>\n",
+      " <tile id=\"2\" x=\"1\" y=\"2\" width=\"6\" height=\"5\">\n",
+      "  <properties>\n",
+      "   <property name=\"name\" value=\"a&amp;b\"/>\n",
+      "  </properties>\n",
+      "  <image source=\"1.png\" width=\"8\" height=\"8\"/>\n",
+      " </tile>\n",
+      "</tileset>\n",
+    ));
+  }
+}
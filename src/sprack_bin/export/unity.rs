@@ -0,0 +1,94 @@
+use super::Exporter;
+use sprack_bin::meta::{AtlasMeta, PageMeta};
+
+use std::fs::File;
+use std::io::{BufWriter, Write, Result};
+use std::path::Path;
+
+// Unity texture importer settings slicing a page into sprites, `<page>.png.meta`.
+// Unity rects start at the bottom left corner of the texture
+pub struct UnityExporter;
+
+impl Exporter for UnityExporter {
+  fn name(&self) -> &'static str { "unity" }
+
+  fn supports_rotation(&self) -> bool { false }
+
+  fn export(&self, meta: &AtlasMeta, dir: &Path) -> Result<()> {
+    for page in &meta.pages {
+      let mut out = BufWriter::new(File::create(dir.join(format!("{}.meta", page.image)))?);
+      writeln!(out, "fileFormatVersion: 2")?;
+      writeln!(out, "guid: {}", guid(page))?;
+      writeln!(out, "TextureImporter:")?;
+      writeln!(out, "  serializedVersion: 12")?;
+      writeln!(out, "  mipmaps:")?;
+      writeln!(out, "    enableMipMap: 0")?;
+      writeln!(out, "  alphaIsTransparency: 1")?;
+      writeln!(out, "  spriteMode: 2")?;
+      writeln!(out, "  spritePixelsToUnits: 100")?;
+      writeln!(out, "  textureType: 8")?;
+      writeln!(out, "  spriteSheet:")?;
+      writeln!(out, "    serializedVersion: 2")?;
+      writeln!(out, "    sprites:")?;
+      for s in &page.sprites {
+        writeln!(out, "    - serializedVersion: 2")?;
+        writeln!(out, "      name: '{}'", s.name.replace('\'', "''"))?;
+        writeln!(out, "      rect:")?;
+        writeln!(out, "        serializedVersion: 2")?;
+        writeln!(out, "        x: {}", s.x)?;
+        writeln!(out, "        y: {}", page.size.h - s.y - s.h)?;
+        writeln!(out, "        width: {}", s.w)?;
+        writeln!(out, "        height: {}", s.h)?;
+        writeln!(out, "      alignment: 0")?;
+        writeln!(out, "      pivot: {{x: 0.5, y: 0.5}}")?;
+        writeln!(out, "      border: {{x: 0, y: 0, z: 0, w: 0}}")?;
+      }
+      out.flush()?;
+    }
+    Ok(())
+  }
+}
+
+// Unity needs one to import the texture. It only depends on the image name, so that reruns with
+// changed sprites keep references to the texture. FNV-1a is fixed, unlike hashers of `std`
+fn guid(page: &PageMeta) -> String {
+  let half = |seed: u8| {
+    Some(seed).iter().chain(page.image.as_bytes())
+      .fold(0xcbf2_9ce4_8422_2325u64, |hash, &byte| (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3))
+  };
+  format!("{:016x}{:016x}", half(0), half(1))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use super::super::draw_rotation;
+  use sprack_bin::meta::Rotation;
+  use sprack_bin::testing::{WorkDir, atlas, page, sprite};
+
+  #[test]
+  fn rects_start_at_bottom_left() {
+    let meta = atlas(Rotation::CounterClockwise, vec![page(0, (48, 80), vec![sprite("hero", 0, (10, 20, 12, 30), false)])]);
+    let dir = WorkDir::new();
+    UnityExporter.export(&meta, dir.path()).unwrap();
+
+    let text = dir.read("0.png.meta");
+    assert!(text.contains("      name: 'hero'\n      rect:\n        serializedVersion: 2\n        x: 10\n        y: 30\n        width: 12\n        height: 30\n"), "{}", text);
+  }
+
+  #[test]
+  fn guid_only_depends_on_image() {
+    let one = page(0, (48, 80), vec![sprite("hero", 0, (10, 20, 12, 30), false)]);
+    let other = page(0, (64, 80), vec![sprite("tree", 0, (0, 0, 8, 8), false), sprite("coin", 0, (8, 0, 8, 8), false)]);
+    assert_eq!(guid(&one), guid(&other));
+    // pinned, so that a new hasher can not silently break references
+    assert_eq!(guid(&one), "027cfd5a52fc6f18baedfa5e5932eb01");
+    assert_ne!(guid(&one), guid(&page(1, (48, 80), vec![])));
+  }
+
+  #[test]
+  fn rotated_sprites_are_refused() {
+    assert!(draw_rotation(&[&UnityExporter], true).is_err());
+    assert_eq!(draw_rotation(&[&UnityExporter], false), Ok(Rotation::CounterClockwise));
+  }
+}
@@ -15,14 +15,14 @@ pub fn new_work_dir() -> Result<PathBuf> {
   Ok(work_dir)
 }
 
-pub fn cleanup_work_dir(path: &AsRef<Path>) {
+pub fn cleanup_work_dir(path: &dyn AsRef<Path>) {
   rm_dir(path).unwrap_or(());
 }
 
-pub fn copy_result_to_out(result_dir: &AsRef<Path>, options: &RunOptions) -> Result<u64> {
+pub fn copy_result_to_out(result_dir: &dyn AsRef<Path>, options: &RunOptions) -> Result<u64> {
   let out = &options.output_path;
   mk_dir(out)?;
-  get_png_files(&result_dir)?.iter().map(|f| copy(&f.path(), &out.join(&f.file_name()))).sum()
+  get_files(&result_dir)?.iter().map(|f| copy(f.path(), out.join(f.file_name()))).sum()
 }
 
 fn gen_work_dir_path() -> PathBuf {
@@ -30,9 +30,10 @@ fn gen_work_dir_path() -> PathBuf {
   temp_dir().join(APP_DIR_NAME).join(rand_name)
 }
 
-fn get_png_files(path: &AsRef<Path>) -> Result<Vec<DirEntry>> {
-  let png_files = read_dir(&path)?.filter(|it| it.is_ok()).map(|it| it.unwrap())
-    .filter(|it| it.path().extension() == Some("png".as_ref()))
+// atlas images along with their metadata
+fn get_files(path: &dyn AsRef<Path>) -> Result<Vec<DirEntry>> {
+  let files = read_dir(path)?.flatten()
+    .filter(|it| it.path().is_file())
     .collect::<Vec<_>>();
-  Ok(png_files)
+  Ok(files)
 }
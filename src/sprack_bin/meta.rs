@@ -0,0 +1,164 @@
+use sprack::PackResult;
+use sprack_bin::drawing::compute_atlas_size;
+use sprack_bin::RunOptions;
+
+use std::collections::HashSet;
+use std::path::Path;
+
+const PNG_EXT: &str = "png";
+
+// everything exporters know about a packing result, coordinates are in pixels of the atlas image
+// with origin at its top left corner
+#[derive(Serialize, Debug)]
+pub struct AtlasMeta {
+  pub heuristic: String,
+  pub rotation: Rotation,
+  pub pages: Vec<PageMeta>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct PageMeta {
+  pub index: usize,
+  // atlas image file name, relative to the metadata
+  pub image: String,
+  pub size: Size,
+  pub sprites: Vec<SpriteMeta>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct SpriteMeta {
+  pub name: String,
+  pub page: usize,
+  // area taken in the atlas, width and height are swapped for rotated sprites
+  pub x: u32,
+  pub y: u32,
+  pub w: u32,
+  pub h: u32,
+  // drawn rotated by 90 degrees in `AtlasMeta::rotation` direction
+  pub rotated: bool,
+  pub original: Size,
+}
+
+#[derive(Serialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Size { pub w: u32, pub h: u32 }
+
+// direction flipped sprites are turned in when drawn into the atlas
+#[derive(Serialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Rotation { Clockwise, CounterClockwise }
+
+impl AtlasMeta {
+  // `names` are indexed the same way as the packed rectangles
+  pub fn new(result: &PackResult, names: &[String], options: &RunOptions) -> AtlasMeta {
+    let pages = result.bins.iter().enumerate().map(|(index, bin)| {
+      let (w, h) = compute_atlas_size(bin, options.pack_options.trim);
+      let sprites = bin.placements.iter().map(|p| {
+        let original = p.rect.non_flipped_size();
+        SpriteMeta {
+          name: names[p.index as usize].clone(),
+          page: index,
+          x: p.rect.x,
+          y: p.rect.y,
+          w: p.rect.size.w,
+          h: p.rect.size.h,
+          rotated: p.rect.flipped,
+          original: Size { w: original.w, h: original.h },
+        }
+      }).collect();
+      PageMeta { index, image: page_image(index), size: Size { w, h }, sprites }
+    }).collect();
+    AtlasMeta { heuristic: result.heuristics.name().to_string(), rotation: options.rotation, pages }
+  }
+}
+
+pub fn page_image(index: usize) -> String {
+  format!("{}.{}", index, PNG_EXT)
+}
+
+// file names without extension, so `sprites/hero.png` becomes `hero`. Formats key frames by name,
+// so repeated ones get a `_2`, `_3`... suffix
+pub fn sprite_names(paths: &[&Path]) -> Vec<String> {
+  let mut taken = HashSet::new();
+  paths.iter()
+    .map(|path| unique_name(&mut taken, &path.file_stem().unwrap_or(path.as_os_str()).to_string_lossy()))
+    .collect()
+}
+
+// `name` itself or the first free of `name_2`, `name_3`..., which is then taken
+pub fn unique_name(taken: &mut HashSet<String>, name: &str) -> String {
+  let unique = (1..).map(|n| if n == 1 { name.to_string() } else { format!("{}_{}", name, n) })
+    .find(|candidate| !taken.contains(candidate))
+    .unwrap();
+  taken.insert(unique.clone());
+  unique
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use sprack::{Bin, Dimension, Placement, Rectangle, heuristic_by_name};
+
+  fn placement(index: u32, x: u32, y: u32, w: u32, h: u32, flipped: bool) -> Placement {
+    Placement { index, rect: Rectangle { x, y, size: Dimension::new(w, h), flipped } }
+  }
+
+  fn result(bins: Vec<Bin>) -> PackResult {
+    PackResult { bins, heuristics: heuristic_by_name("area").unwrap() }
+  }
+
+  fn names(names: &[&str]) -> Vec<String> {
+    names.iter().map(|n| n.to_string()).collect()
+  }
+
+  #[test]
+  fn sprite_names_are_file_stems() {
+    let paths = [Path::new("sprites/hero.png"), Path::new("ui/button.9.png"), Path::new("tree")];
+    assert_eq!(sprite_names(&paths), names(&["hero", "button.9", "tree"]));
+  }
+
+  #[test]
+  fn repeated_sprite_names_get_suffix() {
+    let paths = [Path::new("a/hero.png"), Path::new("b/hero.png"), Path::new("hero_2.png"), Path::new("c/hero.jpg")];
+    assert_eq!(sprite_names(&paths), names(&["hero", "hero_2", "hero_2_2", "hero_3"]));
+  }
+
+  #[test]
+  fn sprites_are_listed_per_page() {
+    let first = Bin::restore(&Dimension::new(64, 32), vec![placement(2, 0, 0, 10, 10, false), placement(0, 10, 0, 4, 6, false)]).unwrap();
+    let second = Bin::restore(&Dimension::new(16, 16), vec![placement(1, 0, 0, 16, 16, false)]).unwrap();
+    let meta = AtlasMeta::new(&result(vec![first, second]), &names(&["a", "b", "c"]), &RunOptions::default());
+
+    assert_eq!(meta.heuristic, "area");
+    assert_eq!(meta.pages.len(), 2);
+    assert_eq!((meta.pages[0].index, meta.pages[0].image.as_str(), meta.pages[0].size), (0, "0.png", Size { w: 64, h: 32 }));
+    assert_eq!((meta.pages[1].index, meta.pages[1].image.as_str(), meta.pages[1].size), (1, "1.png", Size { w: 16, h: 16 }));
+
+    let listed = |page: &PageMeta| page.sprites.iter().map(|s| (s.name.clone(), s.page, s.x, s.y)).collect::<Vec<_>>();
+    assert_eq!(listed(&meta.pages[0]), [("c".to_string(), 0, 0, 0), ("a".to_string(), 0, 10, 0)]);
+    assert_eq!(listed(&meta.pages[1]), [("b".to_string(), 1, 0, 0)]);
+  }
+
+  #[test]
+  fn rotated_sprites_keep_original_size() {
+    let bin = Bin::restore(&Dimension::new(32, 32), vec![placement(0, 2, 3, 6, 10, true), placement(1, 8, 3, 6, 10, false)]).unwrap();
+    let options = RunOptions { rotation: Rotation::Clockwise, ..Default::default() };
+    let meta = AtlasMeta::new(&result(vec![bin]), &names(&["turned", "upright"]), &options);
+    let (turned, upright) = (&meta.pages[0].sprites[0], &meta.pages[0].sprites[1]);
+
+    assert_eq!(meta.rotation, Rotation::Clockwise);
+    assert!(turned.rotated);
+    assert_eq!((turned.x, turned.y, turned.w, turned.h), (2, 3, 6, 10));
+    assert_eq!(turned.original, Size { w: 10, h: 6 });
+    assert!(!upright.rotated);
+    assert_eq!(upright.original, Size { w: 6, h: 10 });
+  }
+
+  #[test]
+  fn trimmed_pages_end_at_last_sprite() {
+    let bin = Bin::restore(&Dimension::new(64, 64), vec![placement(0, 0, 0, 10, 20, false), placement(1, 10, 0, 5, 5, false)]).unwrap();
+    let mut options = RunOptions::default();
+    options.pack_options.trim = true;
+    let meta = AtlasMeta::new(&result(vec![bin]), &names(&["a", "b"]), &options);
+    assert_eq!(meta.pages[0].size, Size { w: 15, h: 20 });
+  }
+}
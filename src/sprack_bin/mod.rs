@@ -1,7 +1,13 @@
 mod drawing;
 mod tool;
 mod fs;
+mod meta;
+mod export;
+#[cfg(test)]
+mod testing;
 
 pub use self::drawing::draw_bin;
 pub use self::tool::RunOptions;
 pub use self::fs::{new_work_dir, cleanup_work_dir, copy_result_to_out};
+pub use self::meta::{AtlasMeta, Rotation, page_image, sprite_names};
+pub use self::export::{Exporter, TemplateExporter, exporter_by_name, exporter_names, draw_rotation};
@@ -0,0 +1,45 @@
+// builders for metadata and a scratch dir shared by unit tests of exporters
+use sprack_bin::fs::{new_work_dir, cleanup_work_dir};
+use sprack_bin::meta::{AtlasMeta, PageMeta, SpriteMeta, Size, Rotation};
+
+use std::fs::{read, read_to_string};
+use std::path::{Path, PathBuf};
+
+// work dir removed once the test is over, even if it fails
+pub struct WorkDir(PathBuf);
+
+impl WorkDir {
+  pub fn new() -> WorkDir {
+    WorkDir(new_work_dir().expect("work dir is created"))
+  }
+
+  pub fn path(&self) -> &Path { &self.0 }
+
+  pub fn read(&self, name: &str) -> String {
+    read_to_string(self.0.join(name)).unwrap_or_else(|e| panic!("{} is written: {}", name, e))
+  }
+
+  pub fn read_bytes(&self, name: &str) -> Vec<u8> {
+    read(self.0.join(name)).unwrap_or_else(|e| panic!("{} is written: {}", name, e))
+  }
+}
+
+impl Drop for WorkDir {
+  fn drop(&mut self) {
+    cleanup_work_dir(&self.0);
+  }
+}
+
+// `w` and `h` are the area in the atlas, so swapped against the original for rotated sprites
+pub fn sprite(name: &str, page: usize, (x, y, w, h): (u32, u32, u32, u32), rotated: bool) -> SpriteMeta {
+  let original = if rotated { Size { w: h, h: w } } else { Size { w, h } };
+  SpriteMeta { name: name.to_string(), page, x, y, w, h, rotated, original }
+}
+
+pub fn page(index: usize, (w, h): (u32, u32), sprites: Vec<SpriteMeta>) -> PageMeta {
+  PageMeta { index, image: format!("{}.png", index), size: Size { w, h }, sprites }
+}
+
+pub fn atlas(rotation: Rotation, pages: Vec<PageMeta>) -> AtlasMeta {
+  AtlasMeta { heuristic: "area".to_string(), rotation, pages }
+}
@@ -1,14 +1,25 @@
 use sprack::PackOptions;
+use sprack_bin::export::{Exporter, DocumentExporter, TemplateExporter};
+use sprack_bin::meta::Rotation;
 
 use std::path::Path;
 
 
 pub struct RunOptions<'a> {
-  pub pack_options: PackOptions<'a>,
+  pub pack_options: PackOptions,
   pub input_paths: Vec<&'a Path>,
   pub output_path: &'a Path,
   pub keep_work_dir: bool,
-  pub demo_run: bool,
+  // pack by opaque cells of this size instead of bounding boxes
+  pub mask_cell: Option<u32>,
+  // metadata formats written next to atlas images
+  pub exporters: Vec<&'static dyn Exporter>,
+  // user format rendered along with the built-in ones
+  pub template: Option<TemplateExporter>,
+  // how flipped sprites are drawn, chosen to suit the formats
+  pub rotation: Rotation,
+  // todo: descend into directories
+  #[allow(dead_code)]
   pub recursive: bool,
 }
 
@@ -19,8 +30,17 @@ impl<'a> Default for RunOptions<'a> {
       input_paths: vec![],
       output_path: Path::new("out"),
       keep_work_dir: false,
-      demo_run: false,
+      mask_cell: None,
+      exporters: vec![&DocumentExporter::Json],
+      template: None,
+      rotation: Rotation::CounterClockwise,
       recursive: false,
     }
   }
 }
+
+impl<'a> RunOptions<'a> {
+  pub fn all_exporters(&self) -> impl Iterator<Item = &dyn Exporter> {
+    self.exporters.iter().cloned().chain(self.template.as_ref().map(|t| t as &dyn Exporter))
+  }
+}